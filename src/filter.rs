@@ -0,0 +1,155 @@
+use super::*;
+
+pub mod biquad;
+pub mod comb;
+pub mod ladder;
+
+/// Lossy integrator, the state is scaled by a per-lane decay factor every sample:
+/// `s[n] = decay * (s[n - 1] + x[n])`. A decay of `1` gives a plain running sum
+#[derive(Clone, Copy, Debug)]
+pub struct LeakyIntegrator<const N: usize>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    s: Simd<f32, N>,
+    decay: Simd<f32, N>,
+}
+
+impl<const N: usize> Default for LeakyIntegrator<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// A plain, lossless, integrator (with a decay of `1`)
+    #[inline]
+    fn default() -> Self {
+        Self::new(Simd::splat(1.))
+    }
+}
+
+impl<const N: usize> LeakyIntegrator<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Creates an integrator with the given decay factor, see [`Self::set_decay`]
+    #[inline]
+    pub fn new(decay: Simd<f32, N>) -> Self {
+        Self {
+            s: Simd::splat(0.),
+            decay,
+        }
+    }
+
+    /// Sets the factor the state is multiplied by every sample, usually in `[0 ; 1]`
+    #[inline]
+    pub fn set_decay(&mut self, decay: Simd<f32, N>) {
+        self.decay = decay;
+    }
+
+    /// Clears the integrator's state, keeping its decay factor
+    #[inline]
+    pub fn reset(&mut self) {
+        self.s = Simd::splat(0.);
+    }
+
+    /// Feeds one sample per lane into the integrator, returning its new state
+    #[inline]
+    pub fn tick(&mut self, sample: Simd<f32, N>) -> Simd<f32, N> {
+        self.s = self.decay * (self.s + sample);
+        self.s
+    }
+
+    /// Returns the integrator's current state
+    #[inline]
+    pub fn get_current(&self) -> Simd<f32, N> {
+        self.s
+    }
+}
+
+/// First difference, the complement of [`LeakyIntegrator`] with a decay of `1`:
+/// `y[n] = x[n] - x[n - 1]`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Differentiator<const N: usize>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    prev: Simd<f32, N>,
+}
+
+impl<const N: usize> Differentiator<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Clears the differentiator's state
+    #[inline]
+    pub fn reset(&mut self) {
+        self.prev = Simd::splat(0.);
+    }
+
+    /// Feeds one sample per lane into the differentiator, returning its difference
+    /// with the previous one
+    #[inline]
+    pub fn tick(&mut self, sample: Simd<f32, N>) -> Simd<f32, N> {
+        let diff = sample - self.prev;
+        self.prev = sample;
+        diff
+    }
+
+    /// Returns the last sample fed into the differentiator
+    #[inline]
+    pub fn get_current(&self) -> Simd<f32, N> {
+        self.prev
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaky_integrator() {
+        let decay = Simd::from_array([1., 0.5, 0.9, 0.]);
+        let mut integrator = LeakyIntegrator::new(decay);
+
+        let mut expected = [0f32; 4];
+        for n in 0..100 {
+            let x = n as f32 * 0.25 - 3.;
+            let y = integrator.tick(Simd::splat(x));
+
+            for (s, d) in expected.iter_mut().zip(decay.to_array()) {
+                *s = d * (*s + x);
+            }
+
+            assert_eq!(y, Simd::from_array(expected));
+            assert_eq!(integrator.get_current(), y);
+        }
+
+        // a decay of 1 is a plain running sum
+        assert_eq!(expected[0], (0..100).map(|n| n as f32 * 0.25 - 3.).sum());
+
+        integrator.reset();
+        assert_eq!(integrator.get_current(), Simd::splat(0.));
+
+        let mut integrator = LeakyIntegrator::<4>::default();
+        for _ in 0..10 {
+            integrator.tick(Simd::splat(0.5));
+        }
+        assert_eq!(integrator.get_current(), Simd::splat(5.));
+    }
+
+    #[test]
+    fn differentiator_inverts_integrator() {
+        let mut integrator = LeakyIntegrator::new(Simd::<f32, 4>::splat(1.));
+        let mut differentiator = Differentiator::default();
+
+        for n in 0..100 {
+            let x = Simd::from_array([n as f32, -2., (n % 7) as f32, (n * n) as f32 * 0.5]);
+            let y = differentiator.tick(integrator.tick(x));
+
+            assert_eq!(y, x);
+            assert_eq!(differentiator.get_current(), integrator.get_current());
+        }
+
+        differentiator.reset();
+        assert_eq!(differentiator.tick(Simd::splat(3.)), Simd::splat(3.));
+    }
+}
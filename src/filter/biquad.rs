@@ -0,0 +1,173 @@
+use super::*;
+
+use simd::StdFloat;
+
+/// Transposed direct form II biquad filter, with one set of coefficients per lane
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Biquad<const N: usize>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    b0: Simd<f32, N>,
+    b1: Simd<f32, N>,
+    b2: Simd<f32, N>,
+    a1: Simd<f32, N>,
+    a2: Simd<f32, N>,
+    s: [Simd<f32, N>; 2],
+}
+
+impl<const N: usize> Biquad<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Creates a filter with the given coefficients, see [`Self::set_coefficients`]
+    #[inline]
+    pub fn new(b: [Simd<f32, N>; 3], a: [Simd<f32, N>; 2]) -> Self {
+        let mut filter = Self::default();
+        filter.set_coefficients(b, a);
+        filter
+    }
+
+    /// Sets the numerator (`b`) and denominator (`a`) coefficients of the transfer function,
+    /// normalized such that `a0 = 1`:
+    ///
+    /// `H(z) = (b[0] + b[1] z^-1 + b[2] z^-2) / (1 + a[0] z^-1 + a[1] z^-2)`
+    #[inline]
+    pub fn set_coefficients(&mut self, b: [Simd<f32, N>; 3], a: [Simd<f32, N>; 2]) {
+        [self.b0, self.b1, self.b2] = b;
+        [self.a1, self.a2] = a;
+    }
+
+    /// Cookbook lowpass filter, with cutoff `w_c` (in radians per sample) and resonance `q`
+    #[inline]
+    pub fn make_lowpass(w_c: Simd<f32, N>, q: Simd<f32, N>) -> Self {
+        let (sin, cos) = math::sin_cos(w_c);
+        let b1 = Simd::splat(1.) - cos;
+        let b0 = b1 * Simd::splat(0.5);
+
+        Self::normalized([b0, b1, b0], Self::cookbook_denominator(sin / (q + q), cos))
+    }
+
+    /// Cookbook highpass filter, with cutoff `w_c` (in radians per sample) and resonance `q`
+    #[inline]
+    pub fn make_highpass(w_c: Simd<f32, N>, q: Simd<f32, N>) -> Self {
+        let (sin, cos) = math::sin_cos(w_c);
+        let b1 = Simd::splat(-1.) - cos;
+        let b0 = b1 * Simd::splat(-0.5);
+
+        Self::normalized([b0, b1, b0], Self::cookbook_denominator(sin / (q + q), cos))
+    }
+
+    /// Cookbook notch filter, with center frequency `w_c` (in radians per sample) and quality factor `q`
+    #[inline]
+    pub fn make_notch(w_c: Simd<f32, N>, q: Simd<f32, N>) -> Self {
+        let (sin, cos) = math::sin_cos(w_c);
+        let one = Simd::splat(1.);
+        let b = [one, cos * Simd::splat(-2.), one];
+
+        Self::normalized(b, Self::cookbook_denominator(sin / (q + q), cos))
+    }
+
+    /// Cookbook peaking (bell) filter, with center frequency `w_c` (in radians per sample),
+    /// quality factor `q`, and gain `gain_db` (in decibels) at the center frequency
+    #[inline]
+    pub fn make_peaking(w_c: Simd<f32, N>, q: Simd<f32, N>, gain_db: Simd<f32, N>) -> Self {
+        let (sin, cos) = math::sin_cos(w_c);
+        let one = Simd::splat(1.);
+
+        // 10^(gain_db / 40)
+        let a = math::exp10_clamped(gain_db * Simd::splat(1. / 40.));
+        let alpha = sin / (q + q);
+        let alpha_a = alpha * a;
+
+        Self::normalized(
+            [one + alpha_a, cos * Simd::splat(-2.), one - alpha_a],
+            Self::cookbook_denominator(alpha / a, cos),
+        )
+    }
+
+    #[inline]
+    fn cookbook_denominator(alpha: Simd<f32, N>, cos: Simd<f32, N>) -> [Simd<f32, N>; 3] {
+        let one = Simd::splat(1.);
+        [one + alpha, cos * Simd::splat(-2.), one - alpha]
+    }
+
+    /// Divides all coefficients by `a[0]`
+    #[inline]
+    fn normalized(b: [Simd<f32, N>; 3], [a0, a1, a2]: [Simd<f32, N>; 3]) -> Self {
+        let a0_recip = a0.recip();
+        Self::new(b.map(|b| b * a0_recip), [a1 * a0_recip, a2 * a0_recip])
+    }
+
+    /// Clears the filter's state, keeping its coefficients
+    #[inline]
+    pub fn reset(&mut self) {
+        self.s = Default::default();
+    }
+
+    /// Feeds one sample per lane into the filter, returning its output
+    #[inline]
+    pub fn process(&mut self, x: Simd<f32, N>) -> Simd<f32, N> {
+        let [s1, s2] = &mut self.s;
+
+        let y = self.b0.mul_add(x, *s1);
+        *s1 = self.b1.mul_add(x, self.a1.mul_add(-y, *s2));
+        *s2 = self.b2.mul_add(x, -self.a2 * y);
+
+        y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const W_C: [f32; 4] = [0.1, 0.5, 1., 2.5];
+    const Q: [f32; 4] = [0.7, 2., 5., 1.];
+
+    /// Steady state gain of `filter` at the frequency `w` (in radians per sample),
+    /// obtained by filtering a sine and a cosine, whose outputs are also in quadrature
+    fn gain_at(filter: Biquad<4>, w: Simd<f32, 4>) -> Simd<f32, 4> {
+        let (mut sin_filter, mut cos_filter) = (filter, filter);
+        let (mut y_sin, mut y_cos) = (Simd::splat(0.), Simd::splat(0.));
+
+        for n in 0..10_000 {
+            let phase = Simd::<f64, 4>::splat(n as f64) * w.cast();
+            y_sin = sin_filter.process(phase.to_array().map(f64::sin).map(|x| x as f32).into());
+            y_cos = cos_filter.process(phase.to_array().map(f64::cos).map(|x| x as f32).into());
+        }
+
+        (y_sin * y_sin + y_cos * y_cos).sqrt()
+    }
+
+    #[test]
+    fn lowpass_dc_gain() {
+        let mut filter = Biquad::make_lowpass(W_C.into(), Q.into());
+
+        let mut y = Simd::splat(0.);
+        for _ in 0..10_000 {
+            y = filter.process(Simd::splat(1.));
+        }
+
+        assert!((y - Simd::splat(1.)).abs().reduce_max() < 1e-5, "{y:?}");
+    }
+
+    #[test]
+    fn notch_null() {
+        let gain = gain_at(Biquad::make_notch(W_C.into(), Q.into()), W_C.into());
+        assert!(gain.reduce_max() < 1e-4, "{gain:?}");
+    }
+
+    #[test]
+    fn peaking_center_gain() {
+        let gain_db = Simd::from_array([6., -12., 3., -24.]);
+        let filter = Biquad::make_peaking(W_C.into(), Q.into(), gain_db);
+
+        let gain = gain_at(filter, W_C.into());
+        let expected = gain_db.to_array().map(|db| 10f32.powf(db / 20.));
+
+        for (gain, expected) in gain.to_array().into_iter().zip(expected) {
+            assert!((gain / expected - 1.).abs() < 1e-4, "{gain} vs {expected}");
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use super::*;
+
+use simd::{cmp::SimdPartialOrd, StdFloat};
+
+/// Feedback comb filter (`y[n] = x[n] + feedback * y[n - delay]`), with one delay
+/// length per lane. All lanes share the same ring buffer of vectors.
+#[derive(Clone, Debug)]
+pub struct CombFilter {
+    buffer: Vec<VFloat>,
+    write_pos: usize,
+    delay: VUInt,
+}
+
+impl CombFilter {
+    const LANE_INDICES: VUInt = {
+        let mut array = [0; FLOATS_PER_VECTOR];
+        let mut i = 0;
+        while i < FLOATS_PER_VECTOR {
+            array[i] = i as u32;
+            i += 1;
+        }
+        Simd::from_array(array)
+    };
+
+    /// Creates a filter able to delay up to `max_delay` samples, with all lanes' delays
+    /// set to `max_delay`.
+    ///
+    /// # Panics
+    ///
+    /// If `max_delay` is `0`, or `max_delay * FLOATS_PER_VECTOR` doesn't fit in an `i32`
+    /// (the gather instructions' index type)
+    #[inline]
+    pub fn new(max_delay: usize) -> Self {
+        let total_len = max_delay.checked_mul(FLOATS_PER_VECTOR);
+        assert!(
+            max_delay != 0 && total_len.is_some_and(|len| i32::try_from(len).is_ok()),
+            "invalid maximum delay length",
+        );
+
+        Self {
+            buffer: vec![Simd::splat(0.); max_delay],
+            write_pos: 0,
+            delay: Simd::splat(max_delay as u32),
+        }
+    }
+
+    /// The maximum delay, in samples, this filter supports
+    #[inline]
+    pub fn max_delay(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Sets the delay length (in samples) of each lane, without allocating.
+    ///
+    /// # Panics
+    ///
+    /// If any lane of `delay` isn't in `[1 ; self.max_delay()]`
+    #[inline]
+    pub fn set_delay_samples(&mut self, delay: VUInt) {
+        let max = VUInt::splat(self.max_delay() as u32);
+        assert!(
+            delay.simd_gt(VUInt::splat(0)).all() && delay.simd_le(max).all(),
+            "delay lengths must be in [1 ; max_delay]",
+        );
+
+        self.delay = delay;
+    }
+
+    /// Zeroes the ring buffer, keeping the delay lengths
+    #[inline]
+    pub fn reset(&mut self) {
+        self.buffer.fill(Simd::splat(0.));
+        self.write_pos = 0;
+    }
+
+    /// Feeds one sample per lane into the filter, returning its output
+    #[inline]
+    pub fn process(&mut self, sample: VFloat, feedback: VFloat) -> VFloat {
+        let len = VUInt::splat(self.buffer.len() as u32);
+
+        // 1 <= delay <= len, so this is in [0 ; 2 * len)
+        let read_pos = VUInt::splat(self.write_pos as u32) + len - self.delay;
+        let read_pos = read_pos.simd_ge(len).select(read_pos - len, read_pos);
+        let offsets = read_pos * VUInt::splat(FLOATS_PER_VECTOR as u32) + Self::LANE_INDICES;
+
+        // SAFETY: read_pos < len, so all offsets are within the buffer
+        let delayed = unsafe { gather_unchecked(self.buffer.as_ptr().cast(), offsets) };
+
+        let output = feedback.mul_add(delayed, sample);
+
+        self.buffer[self.write_pos] = output;
+        self.write_pos += 1;
+        if self.write_pos == self.buffer.len() {
+            self.write_pos = 0;
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f64::consts::TAU;
+
+    /// Steady state gain of a comb filter with the given delays and feedback, at the
+    /// frequency `w` (in radians per sample), obtained by filtering a sine and a cosine
+    fn gain_at(delay: VUInt, feedback: f32, w: Simd<f64, FLOATS_PER_VECTOR>) -> VFloat {
+        let mut sin_filter = CombFilter::new(64);
+        sin_filter.set_delay_samples(delay);
+        let mut cos_filter = sin_filter.clone();
+
+        let feedback = VFloat::splat(feedback);
+        let (mut y_sin, mut y_cos) = (VFloat::splat(0.), VFloat::splat(0.));
+
+        for n in 0..10_000 {
+            let phase = Simd::splat(n as f64) * w;
+            let (sin, cos) = (
+                phase.to_array().map(f64::sin),
+                phase.to_array().map(f64::cos),
+            );
+            y_sin = sin_filter.process(sin.map(|x| x as f32).into(), feedback);
+            y_cos = cos_filter.process(cos.map(|x| x as f32).into(), feedback);
+        }
+
+        (y_sin * y_sin + y_cos * y_cos).sqrt()
+    }
+
+    fn assert_gain(gain: VFloat, expected: f32) {
+        let err = (gain - VFloat::splat(expected)).abs().reduce_max();
+        assert!(err < 1e-4, "{gain:?} vs {expected}");
+    }
+
+    #[test]
+    fn notches_at_multiples_of_fs_over_delay() {
+        let delay = Simd::from_array(core::array::from_fn(|i| 5 + 3 * i as u32));
+        let fundamental = Simd::splat(TAU) / delay.cast();
+
+        // H(z) = 1 / (1 - feedback * z^-delay), so, with a negative feedback, the gain
+        // is minimal at multiples of fs / delay, and maximal halfway between them
+        for k in [1., 2.] {
+            assert_gain(gain_at(delay, -0.5, fundamental * Simd::splat(k)), 1. / 1.5);
+            assert_gain(
+                gain_at(delay, -0.5, fundamental * Simd::splat(k - 0.5)),
+                1. / 0.5,
+            );
+        }
+
+        // and the other way around with a positive feedback
+        assert_gain(gain_at(delay, 0.5, fundamental), 1. / 0.5);
+        assert_gain(
+            gain_at(delay, 0.5, fundamental * Simd::splat(0.5)),
+            1. / 1.5,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid maximum delay length")]
+    fn max_delay_overflowing_gather_indices() {
+        CombFilter::new(i32::MAX as usize / FLOATS_PER_VECTOR + 1);
+    }
+}
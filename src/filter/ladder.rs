@@ -0,0 +1,168 @@
+use super::*;
+
+use simd::StdFloat;
+
+/// Moog style transistor ladder lowpass filter: four cascaded one-pole (TPT) stages,
+/// with a `tanh` saturated feedback path, and one set of parameters per lane
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Ladder<const N: usize>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    g: Simd<f32, N>,
+    k: Simd<f32, N>,
+    norm: Simd<f32, N>,
+    s: [Simd<f32, N>; 4],
+}
+
+impl<const N: usize> Ladder<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Creates a filter with the given parameters, see [`Self::set_params`]
+    #[inline]
+    pub fn new(w_c: Simd<f32, N>, res: Simd<f32, N>) -> Self {
+        let mut filter = Self::default();
+        filter.set_params(w_c, res);
+        filter
+    }
+
+    /// Sets the cutoff `w_c` (in radians per sample, must be in `[0 ; pi)`) and resonance
+    /// `res` (the feedback gain, must be non-negative). The filter self-oscillates when
+    /// `res >= 4`, regardless of the cutoff
+    #[inline]
+    pub fn set_params(&mut self, w_c: Simd<f32, N>, res: Simd<f32, N>) {
+        let one = Simd::splat(1.);
+        let g = math::tan_half_x(w_c);
+        let g = g / (one + g);
+        let g2 = g * g;
+
+        self.g = g;
+        self.k = res;
+        self.norm = res.mul_add(g2 * g2, one).recip();
+    }
+
+    /// Like [`Self::set_params`], but uses [`math::recip_fast`] instead of exact divisions,
+    /// for when the parameters are modulated every sample
+    #[inline]
+    pub fn set_params_fast(&mut self, w_c: Simd<f32, N>, res: Simd<f32, N>) {
+        let one = Simd::splat(1.);
+        let g = math::tan_half_x(w_c);
+        let g = g * math::recip_fast(one + g);
+        let g2 = g * g;
+
+        self.g = g;
+        self.k = res;
+        self.norm = math::recip_fast(res.mul_add(g2 * g2, one));
+    }
+
+    /// Clears the filter's state, keeping its parameters
+    #[inline]
+    pub fn reset(&mut self) {
+        self.s = Default::default();
+    }
+
+    /// Feeds one sample per lane into the filter, returning its output
+    #[inline]
+    pub fn process(&mut self, x: Simd<f32, N>) -> Simd<f32, N> {
+        // each stage outputs g * input + (1 - g) * state, so the ladder's output is
+        // g^4 * u + sigma, with sigma only depending on the states. This resolves the
+        // zero-delay feedback loop for the linearized filter, the saturation is then
+        // applied to the resolved input
+        let sigma = self
+            .s
+            .iter()
+            .fold(Simd::splat(0.), |acc, &s| acc.mul_add(self.g, s))
+            * (Simd::splat(1.) - self.g);
+
+        let mut y = math::tanh(self.k.mul_add(-sigma, x) * self.norm);
+
+        for s in &mut self.s {
+            let v = (y - *s) * self.g;
+            y = v + *s;
+            *s = y + v;
+        }
+
+        y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Peak absolute output of the filter over `len` samples, after feeding it an impulse
+    /// and letting it ring for `skip` samples
+    fn impulse_tail(filter: &mut Ladder<4>, skip: usize, len: usize) -> Simd<f32, 4> {
+        let mut x = Simd::splat(0.01);
+        for _ in 0..skip {
+            filter.process(x);
+            x = Simd::splat(0.);
+        }
+
+        (0..len).fold(Simd::splat(0.), |peak, _| {
+            peak.simd_max(filter.process(x).abs())
+        })
+    }
+
+    #[test]
+    fn dc_gain() {
+        let res = Simd::from_array([0., 1., 2., 3.]);
+        let mut filter = Ladder::new(Simd::splat(0.2), res);
+
+        let x = Simd::splat(1e-3);
+        let mut y = Simd::splat(0.);
+        for _ in 0..10_000 {
+            y = filter.process(x);
+        }
+
+        let expected = x / (Simd::splat(1.) + res);
+        let err = ((y - expected) / expected).abs().reduce_max();
+        assert!(err < 1e-3, "{y:?} vs {expected:?}");
+    }
+
+    #[test]
+    fn resonance_threshold_is_independent_of_cutoff() {
+        for w_c in [0.05, 0.3, 1., 2.5] {
+            let w_c = Simd::splat(w_c);
+
+            let mut filter = Ladder::new(w_c, Simd::splat(3.9));
+            let tail = impulse_tail(&mut filter, 100_000, 1000);
+            assert!(tail.reduce_max() < 1e-6, "w_c = {w_c:?}, tail = {tail:?}");
+
+            let mut filter = Ladder::new(w_c, Simd::splat(4.2));
+            let tail = impulse_tail(&mut filter, 100_000, 1000);
+            assert!(tail.reduce_min() > 1e-2, "w_c = {w_c:?}, tail = {tail:?}");
+        }
+    }
+
+    #[test]
+    fn fast_params_output_difference() {
+        let res = Simd::from_array([0., 1., 2., 3.]);
+        let (mut exact, mut fast) = (Ladder::default(), Ladder::default());
+        let mut max_diff = 0f32;
+
+        for n in 0..100_000 {
+            // cutoff swept between 20 Hz and 20 kHz (at 44.1 kHz), with a sawtooth input
+            let sweep = (n as f32 * 1e-4).sin() * 0.5 + 0.5;
+            let w_c = Simd::splat(20. * 1000f32.powf(sweep) * core::f32::consts::TAU / 44_100.);
+            let x = Simd::splat((n % 100) as f32 * 0.01 - 0.5);
+
+            exact.set_params(w_c, res);
+            fast.set_params_fast(w_c, res);
+
+            let diff = (exact.process(x) - fast.process(x)).abs();
+            max_diff = max_diff.max(diff.reduce_max());
+        }
+
+        assert!(20. * max_diff.log10() < -100., "{max_diff}");
+    }
+
+    #[test]
+    fn reset() {
+        let mut filter = Ladder::new(Simd::splat(1.), Simd::splat(4.2));
+        impulse_tail(&mut filter, 1000, 0);
+        filter.reset();
+        assert_eq!(filter.process(Simd::splat(0.)), Simd::splat(0.));
+    }
+}
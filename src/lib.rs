@@ -16,6 +16,7 @@ use simd::{
     LaneCount, Simd, SupportedLaneCount,
 };
 
+pub mod filter;
 pub mod math;
 pub mod smoothing;
 mod util;
@@ -1,9 +1,22 @@
 use super::*;
 
-use simd::{num::SimdInt, StdFloat};
+use simd::{
+    cmp::{SimdPartialEq, SimdPartialOrd},
+    num::SimdInt,
+    SimdElement, StdFloat,
+};
+
+use core::ops::MulAssign;
+
+#[cfg(all(target_arch = "x86_64", target_feature = "sse"))]
+use core::arch::x86_64::*;
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use core::arch::aarch64::vrecpeq_f32;
 
 const MANTISSA_BITS: u32 = f32::MANTISSA_DIGITS - 1;
 const ONE_BITS: u32 = 1f32.to_bits();
+const SIGN_BIT: u32 = 1 << 31;
 
 #[inline]
 /// lerp innit
@@ -14,146 +27,2524 @@ where
     t.mul_add(b - a, a)
 }
 
-/// "Efficient" `tan(x/2)` approximation. Unspecified results if `|x| >= pi`
+/// Returns `mag` with the sign bit of `sign`, `NAN`s and zeros included, like `f32::copysign`
 #[inline]
-pub fn tan_half_x<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+pub fn copysign<const N: usize>(mag: Simd<f32, N>, sign: Simd<f32, N>) -> Simd<f32, N>
 where
     LaneCount<N>: SupportedLaneCount,
 {
-    // constants
-    let n5 = Simd::splat(0.000_066_137_57);
-    let n3 = Simd::splat(-0.027_777_778);
-    let n1 = Simd::splat(1.);
-    let d4 = Simd::splat(0.001_984_127);
-    let d2 = Simd::splat(-0.222_222_22);
-    let d0 = Simd::splat(2.);
+    let sign_bit = Simd::splat(SIGN_BIT);
+    Simd::from_bits(mag.to_bits() & !sign_bit | sign.to_bits() & sign_bit)
+}
 
-    let x2 = x * x;
-    let den = x2.mul_add(x2.mul_add(d4, d2), d0);
-    let xden = x / den;
-    let num = x2.mul_add(x2.mul_add(n5, n3), n1);
+/// Flips the sign of `x` in lanes where `sign` has its sign bit set, `NAN`s and zeros included.
+/// Unlike [`copysign`], the resulting sign still depends on that of `x`
+#[inline]
+pub fn flip_sign<const N: usize>(x: Simd<f32, N>, sign: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    Simd::from_bits(x.to_bits() ^ sign.to_bits() & Simd::splat(SIGN_BIT))
+}
+
+/// Returns `x` with its sign bit cleared, leaving all other bits (e.g. `NAN` payloads) untouched
+#[inline]
+pub fn abs_bits<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    Simd::from_bits(x.to_bits() & Simd::splat(!SIGN_BIT))
+}
+
+/// Returns `±1` with the sign of `x`, except for `±0` and `NAN` lanes, which are returned as-is
+#[inline]
+pub fn signum_or_zero<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let bits = x.to_bits();
+    let mag = bits & Simd::splat(!SIGN_BIT);
+    let signum = bits & Simd::splat(SIGN_BIT) | Simd::splat(ONE_BITS);
 
-    num * xden
+    let keep = mag.simd_eq(Simd::splat(0)) | mag.simd_gt(Simd::splat(f32::INFINITY.to_bits()));
+    Simd::from_bits(keep.select(bits, signum))
 }
 
-/// Returns `2^i` as a `float`.
-///
-/// Unspecified results if `-126 <= i <= 127` doesn't hold.
+/// Rounds `x` to the nearest integer, with ties rounding to even, like `f32::round_ties_even`
 #[inline]
-pub fn fexp2i<const N: usize>(i: Simd<i32, N>) -> Simd<f32, N>
+pub fn round_ties_even<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
 where
     LaneCount<N>: SupportedLaneCount,
 {
-    Simd::from_bits((i.cast() << MANTISSA_BITS) + Simd::splat(ONE_BITS))
+    // SSE4.1 and NEON have dedicated instructions, which this compiles to.
+    // For some reason, x.round() optimizes badly, but this doesn't
+    #[cfg(any(target_feature = "sse4.1", target_feature = "neon"))]
+    return map(x, f32::round_ties_even);
+
+    // Elsewhere, adding, then subtracting, 2^23 to |x| rounds away its fractional bits
+    // (in the default rounding mode). Lanes where |x| >= 2^23 are already integers, so
+    // they, and NAN lanes, are left untouched
+    #[cfg(not(any(target_feature = "sse4.1", target_feature = "neon")))]
+    return {
+        let magic = Simd::splat((1 << MANTISSA_BITS) as f32);
+        let abs = x.abs();
+        let rounded = (abs + magic) - magic;
+        abs.simd_lt(magic).select(copysign(rounded, x), x)
+    };
 }
 
-/// "Efficient" `exp2` approximation. Unspecified results if `-126 <= v <= 127` doesn't hold.
+/// Returns [`round_ties_even(x)`](round_ties_even), converted to integers
 ///
 /// # Safety
 ///
-/// `v` must be non-NAN, finite, and in the range `[-2^31; 2^31 - 1]`
+/// `x` must be non-NAN, finite, and in the range `[-2^31; 2^31 - 1]`
 #[inline]
-pub unsafe fn exp2<const N: usize>(v: Simd<f32, N>) -> Simd<f32, N>
+pub unsafe fn round_to_int_unchecked<const N: usize>(x: Simd<f32, N>) -> Simd<i32, N>
 where
     LaneCount<N>: SupportedLaneCount,
 {
-    // Taylor series already works pretty well here since
-    // the polynomial approximation we need is only evaluated in [-0.5, 0.5]
-    // (which is small and centered at zero)
+    unsafe { round_ties_even(x).to_int_unchecked() }
+}
 
-    // LN_2^n / n!
-    // constants
-    let a1 = Simd::splat(core::f32::consts::LN_2);
-    let a2 = Simd::splat(0.240_226_5);
-    let a3 = Simd::splat(0.055_504_11);
-    let a4 = Simd::splat(0.009_618_129);
-    let a5 = Simd::splat(0.001_333_355_8);
+/// Wraps `x` into `[0 ; 1)`, e.g. for oscillator phases. Exact for non-negative inputs.
+/// Negative inputs so close to an integer that `x - floor(x)` rounds to `1` return `0`.
+#[inline]
+pub fn wrap01<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let y = x - x.floor();
+    y.simd_lt(Simd::splat(1.)).select(y, Simd::splat(0.))
+}
+
+/// Wraps `x` into `[-0.5 ; 0.5)`. Exact if `|x| < 2^23`.
+#[inline]
+pub fn wrap_signed<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let half = Simd::splat(0.5);
+    let y = x - round_ties_even(x);
+    y.simd_lt(half).select(y, y - Simd::splat(1.))
+}
 
-    // for some reason, v.round() optimizes badly, but this doesn't
-    let rounded = map(v, f32::round_ties_even);
+/// Euclidean remainder of `x` by `m`, computed as `x - |m| * floor(x / |m|)`. Like
+/// [`f32::rem_euclid`], the result is always in `[0 ; |m|)`, negative `m` included.
+/// Unlike it, it isn't exact: the absolute error grows with `|x / m|`.
+#[inline]
+pub fn rem_euclid<const N: usize>(x: Simd<f32, N>, m: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let zero = Simd::splat(0.);
+    let m_abs = m.abs();
 
-    let int = fexp2i(unsafe { rounded.to_int_unchecked() });
+    let r = m_abs.mul_add(-(x / m_abs).floor(), x);
 
-    let x = v - rounded; // is always in [-0.5 ; 0.5]
+    // rounding can push r slightly out of [0 ; |m|)
+    let r = r.simd_lt(zero).select(r + m_abs, r);
+    r.simd_ge(m_abs).select(zero, r)
+}
 
-    let y = x.mul_add(x.mul_add(x.mul_add(x.mul_add(a5, a4), a3), a2), a1);
-    int.mul_add(x * y, int)
+/// Triangle wavefolder: repeatedly reflects `x` back into `[-threshold ; threshold]`,
+/// in closed form. Returns `x` unchanged if it's already in that range. `threshold`
+/// must be positive.
+#[inline]
+pub fn fold<const N: usize>(x: Simd<f32, N>, threshold: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // triangle wave with a period of 4 * threshold, peaking at x = threshold
+    let phase = wrap_signed(x / (threshold * Simd::splat(4.)) - Simd::splat(0.25));
+    let folded = phase.abs().mul_add(Simd::splat(-4.), Simd::splat(1.)) * threshold;
+
+    x.abs().simd_le(threshold).select(x, folded)
 }
 
-/// Returns [`fast_exp2(semitones / 12)`](fast_exp2)
-///
-/// # Safety
+/// 4-point, 3rd-order Hermite (Catmull-Rom) interpolation between `y1` and `y2`,
+/// where `y0` and `y3` are the samples preceding and following them. `t` is the
+/// position between `y1` (`t = 0`) and `y2` (`t = 1`). Reproduces quadratics exactly.
 ///
-/// Same conditions as [`fast_exp2`]
+/// This is Laurent de Soras' formulation. Unlike [`lagrange_interp`], the first
+/// derivative is continuous across segments, which is usually preferable for resampling.
+#[doc(alias = "hermite_interp")]
 #[inline]
-pub unsafe fn semitones_to_ratio<const N: usize>(semitones: Simd<f32, N>) -> Simd<f32, N>
+pub fn cubic_interp<const N: usize>(
+    y0: Simd<f32, N>,
+    y1: Simd<f32, N>,
+    y2: Simd<f32, N>,
+    y3: Simd<f32, N>,
+    t: Simd<f32, N>,
+) -> Simd<f32, N>
 where
     LaneCount<N>: SupportedLaneCount,
 {
-    const RATIO: f32 = 1. / 12.;
-    unsafe { exp2(semitones * Simd::splat(RATIO)) }
+    let half = Simd::splat(0.5);
+
+    let c = (y2 - y0) * half;
+    let v = y1 - y2;
+    let w = c + v;
+    let a = (y3 - y1).mul_add(half, w + v);
+    let b = w + a;
+
+    t.mul_add(t.mul_add(t.mul_add(a, -b), c), y1)
 }
 
-/// Returns `floor(log2(x))` as an `int`. Unspecified results
-/// if `x` is `NAN`, `inf` or non-positive.
+/// 4-point, 3rd-order Lagrange interpolation between `y1` and `y2`, where `y0`
+/// and `y3` are the samples preceding and following them. `t` is the position
+/// between `y1` (`t = 0`) and `y2` (`t = 1`). Reproduces cubics exactly.
 #[inline]
-pub fn ilog2f<const N: usize>(x: Simd<f32, N>) -> Simd<i32, N>
+pub fn lagrange_interp<const N: usize>(
+    y0: Simd<f32, N>,
+    y1: Simd<f32, N>,
+    y2: Simd<f32, N>,
+    y3: Simd<f32, N>,
+    t: Simd<f32, N>,
+) -> Simd<f32, N>
 where
     LaneCount<N>: SupportedLaneCount,
 {
-    ((x.to_bits() - Simd::splat(ONE_BITS)) >> MANTISSA_BITS).cast()
+    let half = Simd::splat(0.5);
+
+    let c2 = (y0 + y2).mul_add(half, -y1);
+    let c3 = (y3 - y0).mul_add(Simd::splat(1. / 6.), (y1 - y2) * half);
+    // the polynomial must reach y2 at t = 1
+    let c1 = y2 - y1 - c2 - c3;
+
+    t.mul_add(t.mul_add(t.mul_add(c3, c2), c1), y1)
 }
 
-/// "Efficient" `log2` approximation. Unspecified results if `v` is
-/// `NAN`, `inf` or non-positive.
+/// Remaps `x` from `[edge0 ; edge1]` to `[0 ; 1]`, clamping the result. Reversed edges
+/// (`edge0 > edge1`) give a decreasing ramp. Lanes where `edge0 == edge1` return `0` if
+/// `x < edge0`, and `1` otherwise.
 #[inline]
-pub fn log2<const N: usize>(v: Simd<f32, N>) -> Simd<f32, N>
+fn step_t<const N: usize>(edge0: Simd<f32, N>, edge1: Simd<f32, N>, x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let zero = Simd::splat(0.);
+    let one = Simd::splat(1.);
+
+    let width = edge1 - edge0;
+    let degenerate = width.simd_eq(zero);
+    // clamping after remapping, rather than before, supports reversed edges
+    let t = ((x - edge0) / degenerate.select(one, width)).simd_clamp(zero, one);
+
+    degenerate.select(x.simd_lt(edge0).select(zero, one), t)
+}
+
+/// Cubic Hermite step between `edge0` and `edge1` (`3t^2 - 2t^3`, where `t` is `x`
+/// remapped from `[edge0 ; edge1]` to `[0 ; 1]`, and clamped). Lanes where `edge0 > edge1`
+/// step down from `1` to `0` instead (`smoothstep(1, 0, x)` is a fade-out). Lanes where
+/// `edge0 == edge1` return a hard step at `edge0`.
+#[inline]
+pub fn smoothstep<const N: usize>(
+    edge0: Simd<f32, N>,
+    edge1: Simd<f32, N>,
+    x: Simd<f32, N>,
+) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let t = step_t(edge0, edge1, x);
+    t * t * t.mul_add(Simd::splat(-2.), Simd::splat(3.))
+}
+
+/// Quintic step between `edge0` and `edge1` (`6t^5 - 15t^4 + 10t^3`), with zero first and
+/// second derivatives at both edges. Same clamping and edge cases as [`smoothstep`].
+#[inline]
+pub fn smootherstep<const N: usize>(
+    edge0: Simd<f32, N>,
+    edge1: Simd<f32, N>,
+    x: Simd<f32, N>,
+) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let t = step_t(edge0, edge1, x);
+    let p = t.mul_add(
+        t.mul_add(Simd::splat(6.), Simd::splat(-15.)),
+        Simd::splat(10.),
+    );
+    t * t * t * p
+}
+
+/// Evaluates the polynomial `coeffs[0] + coeffs[1] * x + ... + coeffs[K - 1] * x^(K - 1)`
+/// using Horner's scheme, with one `mul_add` per coefficient past the last one
+#[inline]
+pub fn poly_horner<T: SimdElement + Default, const N: usize, const K: usize>(
+    x: Simd<T, N>,
+    coeffs: [T; K],
+) -> Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    Simd<T, N>: StdFloat,
+{
+    let Some((&last, rest)) = coeffs.split_last() else {
+        return Simd::default();
+    };
+
+    rest.iter()
+        .rev()
+        .fold(Simd::splat(last), |acc, &c| x.mul_add(acc, Simd::splat(c)))
+}
+
+/// Evaluates the same polynomial as [`poly_horner`], using Estrin's scheme instead, which
+/// has a shorter dependency chain (more instruction-level parallelism) for long polynomials,
+/// at the cost of a few more multiplications. Results may differ from [`poly_horner`] by a
+/// few ULPs.
+#[inline]
+pub fn poly_estrin<T: SimdElement + Default, const N: usize, const K: usize>(
+    x: Simd<T, N>,
+    coeffs: [T; K],
+) -> Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    Simd<T, N>: StdFloat + MulAssign,
+{
+    if K == 0 {
+        return Simd::default();
+    }
+
+    let mut terms = coeffs.map(Simd::splat);
+    let mut len = K;
+    let mut x_pow = x;
+
+    // at each step, c0 + c1 x + c2 x^2 + c3 x^3 + ... becomes
+    // (c0 + c1 x) + (c2 + c3 x) x^2 + ..., a polynomial in x^2 with half as many terms
+    while len > 1 {
+        for i in 0..len / 2 {
+            terms[i] = x_pow.mul_add(terms[2 * i + 1], terms[2 * i]);
+        }
+
+        if len % 2 == 1 {
+            terms[len / 2] = terms[len - 1];
+        }
+
+        len = len.div_ceil(2);
+        x_pow *= x_pow;
+    }
+
+    terms[0]
+}
+
+/// "Efficient" `tan(x/2)` approximation. Unspecified results if `|x| >= pi`
+#[inline]
+pub fn tan_half_x<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
 where
     LaneCount<N>: SupportedLaneCount,
 {
     // constants
-    let a = Simd::splat(-2.632_416_7);
-    let b = Simd::splat(5.);
-    let c = Simd::splat(-3.333_333_3);
-    let d = Simd::splat(1.428_571_5);
-    let e = Simd::splat(-0.333_333_34);
-    let f = Simd::splat(0.032_258_064);
-
-    let log_exponent = ilog2f(v).cast();
-    let x = Simd::<f32, N>::from_bits(
-        v.to_bits() & Simd::splat((1 << MANTISSA_BITS) - 1) | Simd::splat(ONE_BITS),
+    let num_coeffs = [1., -0.027_777_778, 0.000_066_137_57];
+    let den_coeffs = [2., -0.222_222_22, 0.001_984_127];
+
+    let x2 = x * x;
+    let xden = x / poly_horner(x2, den_coeffs);
+
+    poly_horner(x2, num_coeffs) * xden
+}
+
+// `pi` split in two, the first part having enough trailing zero bits
+// for `n * PI_HI` to be exact in range reduction (Cody-Waite style)
+const PI_HI: f32 = 3.140_625;
+const PI_LO: f32 = 0.000_967_653_6;
+
+const FRAC_PI_2_HI: f32 = PI_HI * 0.5;
+const FRAC_PI_2_LO: f32 = PI_LO * 0.5;
+
+/// Flips the sign of the lanes of `x` for which `i` is odd
+#[inline]
+fn flip_sign_if_odd<const N: usize>(x: Simd<f32, N>, i: Simd<i32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    Simd::from_bits(x.to_bits() ^ (i.cast::<u32>() << 31))
+}
+
+/// Degree 7 minimax polynomial for `sin(x)` in `[-pi/2 ; pi/2]`
+#[inline]
+fn sin_poly<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // constants
+    let a1 = Simd::splat(0.999_996_6);
+    let a3 = Simd::splat(-0.166_648_28);
+    let a5 = Simd::splat(0.008_306_325);
+    let a7 = Simd::splat(-0.000_183_636_54);
+
+    let x2 = x * x;
+    x * x2.mul_add(x2.mul_add(x2.mul_add(a7, a5), a3), a1)
+}
+
+/// "Efficient" `sin(x)` approximation. The absolute error is below `8e-7` for `|x| <= 1000`.
+/// The argument is reduced by subtracting the nearest multiple of `pi`, so accuracy degrades
+/// for larger inputs. Unspecified results if `x` isn't finite.
+#[inline]
+pub fn sin<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let n = round_ties_even(x * Simd::splat(core::f32::consts::FRAC_1_PI));
+
+    // sin(r + n * pi) = (-1)^n * sin(r)
+    let r = n.mul_add(Simd::splat(-PI_LO), n.mul_add(Simd::splat(-PI_HI), x));
+
+    flip_sign_if_odd(sin_poly(r), n.cast())
+}
+
+/// "Efficient" `cos(x)` approximation. Same accuracy and conditions as [`sin`].
+#[inline]
+pub fn cos<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let half = Simd::splat(0.5);
+    let n = round_ties_even(x.mul_add(Simd::splat(core::f32::consts::FRAC_1_PI), -half));
+
+    // cos(r + (n + 1/2) * pi) = (-1)^(n + 1) * sin(r)
+    let m = n + half;
+    let r = m.mul_add(Simd::splat(-PI_LO), m.mul_add(Simd::splat(-PI_HI), x));
+
+    flip_sign_if_odd(sin_poly(r), n.cast() + Simd::splat(1))
+}
+
+/// Returns `(sin(x), cos(x))`, sharing the argument reduction, and
+/// computing only one polynomial per function. The absolute error
+/// of both is below `1e-7` for `|x| <= pi`. Same conditions as [`sin`].
+#[inline]
+pub fn sin_cos<const N: usize>(x: Simd<f32, N>) -> (Simd<f32, N>, Simd<f32, N>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // constants
+    let s3 = Simd::splat(-0.166_666_55);
+    let s5 = Simd::splat(0.008_332_161);
+    let s7 = Simd::splat(-0.000_195_152_96);
+    let c4 = Simd::splat(0.041_666_646);
+    let c6 = Simd::splat(-0.001_388_731_6);
+    let c8 = Simd::splat(0.000_024_433_157);
+
+    let n = round_ties_even(x * Simd::splat(core::f32::consts::FRAC_2_PI));
+    let quadrant = n.cast::<i32>();
+
+    // r is always in [-pi/4 ; pi/4]
+    let r = n.mul_add(
+        Simd::splat(-FRAC_PI_2_LO),
+        n.mul_add(Simd::splat(-FRAC_PI_2_HI), x),
+    );
+    let r2 = r * r;
+
+    let s = (r * r2).mul_add(r2.mul_add(r2.mul_add(s7, s5), s3), r);
+    let c = (r2 * r2).mul_add(
+        r2.mul_add(r2.mul_add(c8, c6), c4),
+        r2.mul_add(Simd::splat(-0.5), Simd::splat(1.)),
     );
 
-    let y = x.mul_add(x.mul_add(x.mul_add(x.mul_add(x.mul_add(f, e), d), c), b), a);
-    log_exponent + y
+    // odd quadrants swap sin and cos
+    let swap = (quadrant & Simd::splat(1)).simd_ne(Simd::splat(0));
+    let sin = swap.select(c, s);
+    let cos = swap.select(s, c);
+
+    (
+        flip_sign_if_odd(sin, quadrant >> 1),
+        flip_sign_if_odd(cos, (quadrant + Simd::splat(1)) >> 1),
+    )
 }
 
-/// Returns `fast_exp2(fast_log2(base) * exp)`, or, approximately, `base^exp`
-/// # Safety
+/// "Efficient" `tan(x)` approximation, computed as the ratio of the outputs of
+/// [`sin_cos`]. The relative error is below `3e-7` for `|x| <= 1.5`, and grows
+/// as `|x|` approaches `pi/2`. Unspecified results if `x` isn't finite.
+#[inline]
+pub fn tan<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let (sin, cos) = sin_cos(x);
+    sin / cos
+}
+
+/// `expm1(y)` (`e^y - 1`) for non-negative `y`, built only out of operations that are
+/// monotonic in their inputs (`mul_add`s with non-negative operands, `floor`, `min`, `max`),
+/// so that the result never decreases as `y` increases. The relative error is below `1e-6`
+/// for `y <= 20`, and grows with `y` past that. Unspecified results if `y > 80`.
+#[inline]
+fn expm1_monotonic<const N: usize>(y: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // constants
+    // minimax fit of (expm1(y) - y) / y^2 in [0 ; 1]
+    let small_coeffs = [
+        0.499_999_73,
+        0.166_671_89,
+        0.041_632_17,
+        0.008_436_729,
+        0.001_235_344,
+        0.000_305_955_1,
+    ];
+    // minimax fit of 2^x in [0 ; 1], exact at 0
+    let exp2_coeffs = [
+        1.,
+        core::f32::consts::LN_2,
+        0.240_227_82,
+        0.055_490_92,
+        0.009_665_669,
+        0.001_254_707_9,
+        0.000_213_693_78,
+    ];
+    let one = Simd::splat(1.);
+
+    let small = |y: Simd<f32, N>| y.mul_add(y * poly_horner(y, small_coeffs), y);
+
+    // floor, unlike rounding, keeps the fractional part, and thus all the terms of the
+    // polynomial, non-negative. Capping it at 2 makes it meet 2^(i + 1) * p(0) = 2^(i + 1)
+    let v = y * Simd::splat(core::f32::consts::LOG2_E);
+    let i = v.floor();
+    let p = poly_horner(v - i, exp2_coeffs).simd_min(Simd::splat(2.));
+    let large = p.mul_add(fexp2i(i.cast()), -one);
+
+    // e^y - 1 cancels out for small y, where the first polynomial is used instead.
+    // The max makes sure that both halves meet at y = 1 without going backwards
+    y.simd_lt(one).select(small(y), large.simd_max(small(one)))
+}
+
+/// "Efficient" `tanh(x)` approximation, computed as `1 / (1 + 2 / expm1(2|x|))`, with the
+/// sign of `x` copied back, and an error below 4 ULPs. Every step of the computation is
+/// monotonic, so the result is odd, and never decreases, even from one float to the next.
+/// It reaches `±1` for `|x| > 8.665`, and `NAN` lanes are returned as-is.
+#[inline]
+pub fn tanh<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // tanh(x) rounds to x below this
+    let tiny = Simd::splat(1. / 4096.);
+    let one = Simd::splat(1.);
+
+    // past 10, the result rounds to 1 anyway
+    let x_abs = x.abs().simd_min(Simd::splat(10.));
+    let y = (one + Simd::splat(2.) / expm1_monotonic(x_abs + x_abs)).recip();
+
+    // 2 / expm1(2|x|) overflows for very small |x|. The max keeps
+    // the result from going backwards at the threshold
+    let y = x_abs.simd_lt(tiny).select(x_abs, y.simd_max(tiny));
+
+    x.is_nan().select(x, copysign(y, x))
+}
+
+/// Degree 11 minimax polynomial for `atan(x)` in `[-1 ; 1]`, exact at `±1`
+#[inline]
+fn atan_poly<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // constants
+    let a1 = Simd::splat(0.999_975_7);
+    let a3 = Simd::splat(-0.332_585_2);
+    let a5 = Simd::splat(0.193_293_69);
+    let a7 = Simd::splat(-0.115_781_97);
+    let a9 = Simd::splat(0.051_923_487);
+    let a11 = Simd::splat(-0.011_427_526);
+
+    let x2 = x * x;
+    let y = x2.mul_add(x2.mul_add(x2.mul_add(x2.mul_add(a11, a9), a7), a5), a3);
+    x * x2.mul_add(y, a1)
+}
+
+/// "Efficient" `atan(x)` approximation, with an absolute error below `2e-6`.
+/// Inputs outside of `[-1 ; 1]` use the identity `atan(x) = ±pi/2 - atan(1/x)`.
+#[inline]
+pub fn atan<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let x_abs = x.abs();
+    let one = Simd::splat(1.);
+
+    let invert = x_abs.simd_gt(one);
+    let a = atan_poly(invert.select(one / x_abs, x_abs));
+    let a = invert.select(Simd::splat(core::f32::consts::FRAC_PI_2) - a, a);
+
+    copysign(a, x)
+}
+
+/// "Efficient" `atan2(y, x)` approximation, with an absolute error below `3e-6`.
 ///
-/// Same conditions as [`fast_exp2`].
+/// Zeros (including their signs), infinities and `NAN`s are handled the same way
+/// [`f32::atan2`] handles them. In particular, at the origin, `atan2(±0, +0) = ±0`
+/// and `atan2(±0, -0) = ±pi`.
 #[inline]
-pub unsafe fn pow<const N: usize>(base: Simd<f32, N>, exp: Simd<f32, N>) -> Simd<f32, N>
+pub fn atan2<const N: usize>(y: Simd<f32, N>, x: Simd<f32, N>) -> Simd<f32, N>
 where
     LaneCount<N>: SupportedLaneCount,
 {
-    exp2(log2(base) * exp)
+    let (x_abs, y_abs) = (x.abs(), y.abs());
+    let max = x_abs.simd_max(y_abs);
+
+    // fold everything into the first octant, both the
+    // `±0 / ±0` and `±inf / ±inf` cases produce NAN here
+    let octant_swap = y_abs.simd_gt(x_abs);
+    let t = x_abs.simd_min(y_abs) / max;
+    let t = x_abs.simd_eq(y_abs).select(Simd::splat(1.), t);
+    let t = max.simd_eq(Simd::splat(0.)).select(Simd::splat(0.), t);
+    let a = atan_poly(t);
+
+    let pi_2 = Simd::splat(core::f32::consts::FRAC_PI_2);
+    let a = octant_swap.select(pi_2 - a, a);
+    let a = x
+        .is_sign_negative()
+        .select(Simd::splat(core::f32::consts::PI) - a, a);
+
+    let a = copysign(a, y);
+
+    (x.is_nan() | y.is_nan()).select(x + y, a)
 }
 
+/// Returns `sqrt(x^2 + y^2)`, without overflowing or underflowing in intermediate results,
+/// by scaling by the larger magnitude first. Like [`f32::hypot`], lanes where `x` or `y`
+/// is infinite return `+inf`, even if the other one is `NAN`. Other `NAN` lanes return `NAN`.
 #[inline]
-pub fn flp_to_fxp<const N: usize>(x: Simd<f32, N>) -> Simd<u32, N>
+pub fn hypot<const N: usize>(x: Simd<f32, N>, y: Simd<f32, N>) -> Simd<f32, N>
 where
     LaneCount<N>: SupportedLaneCount,
 {
-    const RATIO: f32 = (1u64 << u32::BITS) as f32;
-    unsafe { (x * Simd::splat(RATIO)).to_int_unchecked() }
+    let zero = Simd::splat(0.);
+    let inf = Simd::splat(f32::INFINITY);
+
+    let (x_abs, y_abs) = (x.abs(), y.abs());
+    let max = x_abs.simd_max(y_abs);
+    let min = x_abs.simd_min(y_abs);
+
+    // min <= max, so ratio is in [0 ; 1]
+    let ratio = max.simd_eq(zero).select(zero, min / max);
+    let h = max * ratio.mul_add(ratio, Simd::splat(1.)).sqrt();
+
+    let h = (x.is_nan() | y.is_nan()).select(Simd::splat(f32::NAN), h);
+    (x_abs.simd_eq(inf) | y_abs.simd_eq(inf)).select(inf, h)
 }
 
+/// "Efficient" cube root approximation, preserving the sign of `x` (unlike [`pow`]), with a
+/// relative error below `1.5e-6`, subnormals included. Zeros and infinities are returned as-is.
 #[inline]
-pub fn fxp_to_flp<const N: usize>(x: Simd<u32, N>) -> Simd<f32, N>
+pub fn cbrt<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
 where
     LaneCount<N>: SupportedLaneCount,
 {
-    const RATIO: f32 = 1. / (1u64 << u32::BITS) as f32;
-    x.cast() * Simd::splat(RATIO)
+    // constants
+    // bits of the initial guess, as for the "fast inverse square root" trick
+    let magic = Simd::splat(0x2a51_4067);
+    let third = Simd::splat(1. / 3.);
+
+    let x_abs = x.abs();
+
+    // subnormals are scaled by 2^24, so that their cube root is scaled by 2^8
+    let subnormal = x_abs.to_bits().simd_lt(Simd::splat(1 << MANTISSA_BITS));
+    let a = subnormal.select(x_abs * Simd::splat((1 << 24) as f32), x_abs);
+
+    let mut y = Simd::<f32, N>::from_bits(a.to_bits() / Simd::splat(3) + magic);
+
+    // Newton-Raphson steps: y = (2y + a / y^2) / 3
+    for _ in 0..2 {
+        y = (y + y + a / (y * y)) * third;
+    }
+
+    let y = subnormal.select(y * Simd::splat(1. / (1 << 8) as f32), y);
+    let y = copysign(y, x);
+
+    (x_abs.simd_eq(Simd::splat(0.)) | x_abs.simd_eq(Simd::splat(f32::INFINITY))).select(x, y)
+}
+
+/// Converts frequencies in Hz to angular frequencies in radians per sample, i.e.
+/// `2 * pi * freq / sample_rate`, clamped to `[0 ; 0.98 * pi]`, so that the result
+/// always lies in the domain of [`tan_half_x`], even for frequencies above Nyquist.
+#[inline]
+pub fn hz_to_w_c<const N: usize>(freq: Simd<f32, N>, sample_rate: f32) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    const MAX_W_C: f32 = 0.98 * core::f32::consts::PI;
+
+    let w_c = freq * Simd::splat(core::f32::consts::TAU / sample_rate);
+    w_c.simd_clamp(Simd::splat(0.), Simd::splat(MAX_W_C))
+}
+
+/// Applies `f` to `x`, `M` lanes at a time, padding the last chunk with ones
+#[inline]
+fn map_chunks<const N: usize, const M: usize>(
+    x: Simd<f32, N>,
+    f: impl Fn(Simd<f32, M>) -> Simd<f32, M>,
+) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    LaneCount<M>: SupportedLaneCount,
+{
+    let mut lanes = x.to_array();
+
+    for chunk in lanes.chunks_mut(M) {
+        let mut v = Simd::splat(1.);
+        v.as_mut_array()[..chunk.len()].copy_from_slice(chunk);
+        chunk.copy_from_slice(&f(v).as_array()[..chunk.len()]);
+    }
+
+    Simd::from_array(lanes)
+}
+
+/// Low precision `1 / x` estimate, using the dedicated instructions where available
+#[inline]
+fn recip_estimate<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+    return map_chunks::<N, 16>(x, |x| unsafe { _mm512_rcp14_ps(x.into()) }.into());
+
+    #[cfg(all(
+        target_arch = "x86_64",
+        not(target_feature = "avx512f"),
+        target_feature = "avx"
+    ))]
+    return map_chunks::<N, 8>(x, |x| unsafe { _mm256_rcp_ps(x.into()) }.into());
+
+    #[cfg(all(
+        target_arch = "x86_64",
+        not(target_feature = "avx"),
+        target_feature = "sse"
+    ))]
+    return map_chunks::<N, 4>(x, |x| unsafe { _mm_rcp_ps(x.into()) }.into());
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    return map_chunks::<N, 4>(x, |x| unsafe { vrecpeq_f32(x.into()) }.into());
+
+    // same idea as the "fast inverse square root" trick
+    #[cfg(not(any(
+        all(target_arch = "x86_64", target_feature = "sse"),
+        all(target_arch = "aarch64", target_feature = "neon"),
+    )))]
+    return Simd::from_bits(Simd::splat(0x7ef3_11c7) - x.to_bits());
+}
+
+/// "Efficient" `1 / x` approximation, refining the hardware reciprocal estimate (`rcpps`,
+/// or `vrcp14ps` with AVX-512, on x86_64, and `frecpe` on aarch64) with Newton-Raphson
+/// iterations. Elsewhere, the initial estimate is an integer subtraction on the bits of `x`.
+/// The relative error is below `3e-7`. Unspecified results if `x` is zero, subnormal, `NAN`
+/// or infinite, or if `1 / x` is.
+#[inline]
+pub fn recip_fast<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // each step doubles the number of correct bits of the estimate, which is
+    // about 12 on x86 (14 with AVX-512), 8 with NEON, and 4 with the bit trick
+    const STEPS: usize = if cfg!(all(target_arch = "x86_64", target_feature = "sse")) {
+        1
+    } else if cfg!(all(target_arch = "aarch64", target_feature = "neon")) {
+        2
+    } else {
+        3
+    };
+
+    let one = Simd::splat(1.);
+    let mut y = recip_estimate(x);
+
+    for _ in 0..STEPS {
+        y = y.mul_add((-x).mul_add(y, one), y);
+    }
+
+    y
+}
+
+/// Returns `2^i` as a `float`.
+///
+/// Unspecified results if `-126 <= i <= 127` doesn't hold.
+#[inline]
+pub fn fexp2i<const N: usize>(i: Simd<i32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    Simd::from_bits((i.cast() << MANTISSA_BITS) + Simd::splat(ONE_BITS))
+}
+
+/// "Efficient" `exp2` approximation. Unspecified results if `-126 <= v <= 127` doesn't hold.
+///
+/// # Safety
+///
+/// `v` must be non-NAN, finite, and in the range `[-2^31; 2^31 - 1]`
+#[inline]
+pub unsafe fn exp2<const N: usize>(v: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // Taylor series already works pretty well here since
+    // the polynomial approximation we need is only evaluated in [-0.5, 0.5]
+    // (which is small and centered at zero)
+
+    // LN_2^n / n!
+    // constants
+    let coeffs = [
+        core::f32::consts::LN_2,
+        0.240_226_5,
+        0.055_504_11,
+        0.009_618_129,
+        0.001_333_355_8,
+    ];
+
+    let (int, x) = exp2_split(v);
+    let y = poly_horner(x, coeffs);
+    int.mul_add(x * y, int)
+}
+
+/// Splits `v` into `2^round(v)`, and `v - round(v)`, which is always in `[-0.5 ; 0.5]`
+///
+/// # Safety
+///
+/// Same as [`exp2`]
+#[inline]
+unsafe fn exp2_split<const N: usize>(v: Simd<f32, N>) -> (Simd<f32, N>, Simd<f32, N>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let rounded = round_ties_even(v);
+
+    (fexp2i(unsafe { rounded.to_int_unchecked() }), v - rounded)
+}
+
+/// More precise, but slightly slower, variant of [`exp2`], using a higher degree minimax
+/// polynomial. The error is below 2 ULPs when the output is a normal float.
+/// Unspecified results if `-126 <= v <= 127` doesn't hold.
+///
+/// # Safety
+///
+/// Same as [`exp2`]
+#[inline]
+pub unsafe fn exp2_precise<const N: usize>(v: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // minimax fit of (2^x - 1) / x in [-0.5 ; 0.5], for relative error
+    // constants
+    let coeffs = [
+        core::f32::consts::LN_2,
+        0.240_226_5,
+        0.055_503_574,
+        0.009_618_239,
+        0.001_339_074_5,
+        0.000_154_030_56,
+    ];
+
+    let (int, x) = exp2_split(v);
+    let y = poly_horner(x, coeffs);
+    int.mul_add(x * y, int)
+}
+
+/// Safe variant of [`exp2`], `v` is clamped to `[-126 ; 127]`
+/// beforehand, with `NAN` lanes mapped to `-126`. So, `2^-126` and
+/// `2^127` are returned for lanes that are out of range, or `NAN`.
+#[inline]
+pub fn exp2_clamped<const N: usize>(v: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // simd_max returns the other operand if one of them is NAN
+    let v = v.simd_max(Simd::splat(-126.)).simd_min(Simd::splat(127.));
+    // SAFETY: v is finite and in [-126 ; 127]
+    unsafe { exp2(v) }
+}
+
+/// "Efficient" `exp` approximation, returns [`exp2(v * log2(e))`](exp2).
+///
+/// # Safety
+///
+/// Same conditions as [`exp2`], for `v * log2(e)`
+#[inline]
+pub unsafe fn exp<const N: usize>(v: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    unsafe { exp2(v * Simd::splat(core::f32::consts::LOG2_E)) }
+}
+
+/// "Efficient" `10^v` approximation, returns [`exp2(v * log2(10))`](exp2).
+///
+/// # Safety
+///
+/// Same conditions as [`exp2`], for `v * log2(10)`
+#[inline]
+pub unsafe fn exp10<const N: usize>(v: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    unsafe { exp2(v * Simd::splat(core::f32::consts::LOG2_10)) }
+}
+
+/// Safe variant of [`exp`], built on [`exp2_clamped`]. So, results are
+/// clamped to `[2^-126 ; 2^127]`, and `NAN` lanes return `2^-126`.
+#[inline]
+pub fn exp_clamped<const N: usize>(v: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    exp2_clamped(v * Simd::splat(core::f32::consts::LOG2_E))
+}
+
+/// Safe variant of [`exp10`], built on [`exp2_clamped`]. So, results are
+/// clamped to `[2^-126 ; 2^127]`, and `NAN` lanes return `2^-126`.
+#[inline]
+pub fn exp10_clamped<const N: usize>(v: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    exp2_clamped(v * Simd::splat(core::f32::consts::LOG2_10))
+}
+
+/// "Efficient" `sinh` approximation, computed from a single call to [`exp`]. Lanes where
+/// `|x| < 1` use a Taylor polynomial instead, avoiding the cancellation in `e^x - e^-x`,
+/// so the relative error stays below `5e-6` everywhere. Unspecified results if `|x| > 87`.
+///
+/// # Safety
+///
+/// Same conditions as [`exp`]
+#[inline]
+pub unsafe fn sinh<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // constants
+    let a3 = Simd::splat(1. / 6.);
+    let a5 = Simd::splat(1. / 120.);
+    let a7 = Simd::splat(1. / 5040.);
+    let a9 = Simd::splat(1. / 362_880.);
+    let half = Simd::splat(0.5);
+
+    let x2 = x * x;
+    let small = (x * x2).mul_add(x2.mul_add(x2.mul_add(x2.mul_add(a9, a7), a5), a3), x);
+
+    let e = unsafe { exp(x) };
+    let large = (e - e.recip()) * half;
+
+    x.abs().simd_lt(Simd::splat(1.)).select(small, large)
+}
+
+/// "Efficient" `cosh` approximation, computed from a single call to [`exp`],
+/// with the same accuracy. Unspecified results if `|x| > 87`.
+///
+/// # Safety
+///
+/// Same conditions as [`exp`]
+#[inline]
+pub unsafe fn cosh<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let e = unsafe { exp(x) };
+    (e + e.recip()) * Simd::splat(0.5)
+}
+
+/// "Efficient" `asinh` approximation, computed as `ln(|x| + sqrt(x^2 + 1))`, with the
+/// sign of `x` copied back. Lanes where `|x| < 0.25` use a Taylor polynomial instead.
+/// Absolute error below `3e-6`. Unspecified results if `|x| > 1e18`.
+#[inline]
+pub fn asinh<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // constants
+    let a3 = Simd::splat(-1. / 6.);
+    let a5 = Simd::splat(3. / 40.);
+    let a7 = Simd::splat(-5. / 112.);
+    let a9 = Simd::splat(35. / 1152.);
+    let one = Simd::splat(1.);
+
+    let x_abs = x.abs();
+    let x2 = x * x;
+
+    let small = (x_abs * x2).mul_add(x2.mul_add(x2.mul_add(x2.mul_add(a9, a7), a5), a3), x_abs);
+    let large = ln(x_abs + (x2 + one).sqrt());
+    let y = x_abs.simd_lt(Simd::splat(0.25)).select(small, large);
+
+    copysign(y, x)
+}
+
+/// Soft clipper, returns `asinh(drive * x) / asinh(drive)`, which is odd, passes through
+/// `±1` at `±1`, and tends to the identity as `drive` tends to `0`. `drive` must be positive.
+#[inline]
+pub fn asinh_clip<const N: usize>(x: Simd<f32, N>, drive: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    asinh(drive * x) / asinh(drive)
+}
+
+/// Returns [`fast_exp2(semitones / 12)`](fast_exp2)
+///
+/// # Safety
+///
+/// Same conditions as [`fast_exp2`]
+#[inline]
+pub unsafe fn semitones_to_ratio<const N: usize>(semitones: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    const RATIO: f32 = 1. / 12.;
+    unsafe { exp2(semitones * Simd::splat(RATIO)) }
+}
+
+/// Like [`semitones_to_ratio`], but using [`exp2_precise`], e.g. for tuning tables
+///
+/// # Safety
+///
+/// Same conditions as [`exp2_precise`]
+#[inline]
+pub unsafe fn semitones_to_ratio_precise<const N: usize>(semitones: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    const RATIO: f32 = 1. / 12.;
+    unsafe { exp2_precise(semitones * Simd::splat(RATIO)) }
+}
+
+/// Returns [`exp2_precise(cents / 1200)`](exp2_precise)
+///
+/// # Safety
+///
+/// Same conditions as [`exp2_precise`]
+#[inline]
+pub unsafe fn cents_to_ratio<const N: usize>(cents: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    const RATIO: f32 = 1. / 1200.;
+    unsafe { exp2_precise(cents * Simd::splat(RATIO)) }
+}
+
+/// Inverse of [`semitones_to_ratio`], returns [`log2(ratio) * 12`](log2).
+/// Same conditions as [`log2`], for `ratio`.
+#[inline]
+pub fn ratio_to_semitones<const N: usize>(ratio: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    log2(ratio) * Simd::splat(12.)
+}
+
+/// Inverse of [`cents_to_ratio`], returns [`log2(ratio) * 1200`](log2).
+/// Same conditions as [`log2`], for `ratio`.
+#[inline]
+pub fn ratio_to_cents<const N: usize>(ratio: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    log2(ratio) * Simd::splat(1200.)
+}
+
+/// Converts (possibly fractional) MIDI note numbers to frequencies in Hz, with
+/// A4 (`69`) at 440 Hz. Returns `440 * semitones_to_ratio(note - 69)`.
+///
+/// # Safety
+///
+/// Same conditions as [`semitones_to_ratio`], for `note - 69`
+#[inline]
+pub unsafe fn note_to_freq<const N: usize>(note: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    unsafe { semitones_to_ratio(note - Simd::splat(69.)) * Simd::splat(440.) }
+}
+
+/// Like [`note_to_freq`], but returns frequencies normalized by `sample_rate`.
+///
+/// # Safety
+///
+/// Same conditions as [`note_to_freq`]
+#[inline]
+pub unsafe fn note_to_norm_freq<const N: usize>(
+    note: Simd<f32, N>,
+    sample_rate: f32,
+) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    unsafe { semitones_to_ratio(note - Simd::splat(69.)) * Simd::splat(440. / sample_rate) }
+}
+
+/// Scales subnormal (and zero) lanes of `x` by `2^23`, so that their exponent bits
+/// become meaningful, returning the scaled vector, and the exponent offset to subtract
+#[inline]
+fn normalize_subnormals<const N: usize>(x: Simd<f32, N>) -> (Simd<f32, N>, Simd<i32, N>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // exponent bits are all zero (for non-negative x)
+    let subnormal = x.to_bits().simd_lt(Simd::splat(1 << MANTISSA_BITS));
+
+    (
+        subnormal.select(x * Simd::splat((1 << MANTISSA_BITS) as f32), x),
+        subnormal.select(Simd::splat(MANTISSA_BITS as i32), Simd::splat(0)),
+    )
+}
+
+/// Like [`ilog2f`], without handling subnormals
+#[inline]
+fn ilog2f_normal<const N: usize>(x: Simd<f32, N>) -> Simd<i32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    const EXP_BIAS: i32 = (ONE_BITS >> MANTISSA_BITS) as i32;
+    (x.to_bits() >> MANTISSA_BITS).cast() - Simd::splat(EXP_BIAS)
+}
+
+/// Returns `floor(log2(x))` as an `int`, subnormals included. Zero lanes return `-150`.
+/// Unspecified results if `x` is `NAN`, `inf` or negative.
+#[inline]
+pub fn ilog2f<const N: usize>(x: Simd<f32, N>) -> Simd<i32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let (x, offset) = normalize_subnormals(x);
+    ilog2f_normal(x) - offset
+}
+
+/// "Efficient" `log2` approximation, subnormals included. Zero lanes return `-150`.
+/// Unspecified results if `v` is `NAN`, `inf` or negative.
+#[inline]
+pub fn log2<const N: usize>(v: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // constants
+    let coeffs = [
+        -0.442_544_94,
+        0.275_600_3,
+        -0.181_948_88,
+        0.095_956_46,
+        -0.025_841_45,
+    ];
+    let one = Simd::splat(1.);
+
+    let (v, offset) = normalize_subnormals(v);
+    let log_exponent = (ilog2f_normal(v) - offset).cast();
+    let x = Simd::<f32, N>::from_bits(
+        v.to_bits() & Simd::splat((1 << MANTISSA_BITS) - 1) | Simd::splat(ONE_BITS),
+    );
+
+    // log2(1 + t) = t + t * (t - 1) * p(t), so that powers of two are exact
+    let t = x - one;
+    let p = poly_horner(t, coeffs);
+    log_exponent + (t * (t - one)).mul_add(p, t)
+}
+
+/// Like [`log2`], but with IEEE-style results for special values: `±0` lanes return
+/// `-inf`, `+inf` lanes return `+inf`, and negative and `NAN` lanes return `NAN`.
+#[inline]
+pub fn log2_ieee<const N: usize>(v: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let zero = Simd::splat(0.);
+    let inf = Simd::splat(f32::INFINITY);
+
+    // also false for NAN lanes
+    let non_negative = v.simd_ge(zero);
+
+    let y = v.simd_eq(inf).select(inf, log2(v));
+    let y = v.simd_eq(zero).select(-inf, y);
+    non_negative.select(y, Simd::splat(f32::NAN))
+}
+
+/// "Efficient" natural logarithm approximation, returns [`log2(v) * ln(2)`](log2).
+///
+/// Like [`log2`], this is only meaningful for positive, finite `v`. Zero, negative,
+/// infinite and `NAN` lanes never produce `NAN`s or infinities, but arbitrary
+/// finite values (e.g. `ln(0)` returns `-150 * ln(2)`), and should be masked out,
+/// or floored, like in [`gain_to_db_floored`], by the caller.
+#[inline]
+pub fn ln<const N: usize>(v: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    log2(v) * Simd::splat(core::f32::consts::LN_2)
+}
+
+/// "Efficient" `log10` approximation, returns [`log2(v) * log10(2)`](log2).
+///
+/// Same behavior as [`ln`] for zero, negative, infinite and `NAN` lanes.
+#[inline]
+pub fn log10<const N: usize>(v: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    log2(v) * Simd::splat(core::f32::consts::LOG10_2)
+}
+
+/// Inverse of [`note_to_freq`], returns `69 + 12 * log2(freq / 440)`, so
+/// A4 and the frequencies of its octaves map to exact note numbers.
+/// Same conditions as [`log2`], for `freq`.
+#[inline]
+pub fn freq_to_note<const N: usize>(freq: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    log2(freq / Simd::splat(440.)).mul_add(Simd::splat(12.), Simd::splat(69.))
+}
+
+/// Returns `fast_exp2(fast_log2(base) * exp)`, or, approximately, `base^exp`
+/// # Safety
+///
+/// Same conditions as [`fast_exp2`].
+#[inline]
+pub unsafe fn pow<const N: usize>(base: Simd<f32, N>, exp: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    exp2(log2(base) * exp)
+}
+
+/// Like [`pow`], but with defined results for zero and negative bases:
+///
+/// - `±0^exp` returns `0` if `exp > 0`, `1` if `exp == 0`, and `inf` if `exp < 0`, infinite
+///   exponents included, and `NAN` if `exp` is `NAN`.
+/// - Negative bases with integer exponents return `±|base|^exp`, negative for odd exponents.
+/// - Negative bases with non-integer exponents return `NAN`.
+///
+/// # Safety
+///
+/// Same conditions as [`exp2`], for `log2(|base|) * exp`, in non-zero `base` lanes. Zero
+/// `base` lanes accept any `exp`.
+#[inline]
+pub unsafe fn powf_signed<const N: usize>(base: Simd<f32, N>, exp: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let zero = Simd::splat(0.);
+    let one = Simd::splat(1.);
+
+    let base_abs = base.abs();
+    let base_zero = base_abs.simd_eq(zero);
+    let negative = base.is_sign_negative() & !base_zero;
+
+    // zero lanes are computed as 1^0 to stay in the domain of exp2, whatever exp is
+    let y = exp2(log2(base_zero.select(one, base_abs)) * base_zero.select(zero, exp));
+
+    let half_exp = exp * Simd::splat(0.5);
+    let is_int = exp.trunc().simd_eq(exp);
+    let is_odd = is_int & half_exp.trunc().simd_ne(half_exp);
+
+    let y = (negative & is_odd).select(-y, y);
+    let y = (negative & !is_int).select(Simd::splat(f32::NAN), y);
+
+    let zero_base_result = exp.simd_lt(zero).select(Simd::splat(f32::INFINITY), zero);
+    let zero_base_result = exp.is_nan().select(exp, zero_base_result);
+    (base_zero & exp.simd_ne(zero)).select(zero_base_result, y)
+}
+
+/// Converts decibels to linear gain, returns [`exp2(db * log2(10) / 20)`](exp2),
+/// or, approximately, `10^(db / 20)`. Same accuracy as [`exp2`].
+///
+/// # Safety
+///
+/// Same conditions as [`exp2`], for `db * log2(10) / 20`
+#[inline]
+pub unsafe fn db_to_gain<const N: usize>(db: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    const RATIO: f32 = core::f32::consts::LOG2_10 / 20.;
+    unsafe { exp2(db * Simd::splat(RATIO)) }
+}
+
+/// Converts linear gain to decibels, returns [`log2(gain) * 20 * log10(2)`](log2),
+/// or, approximately, `20 * log10(gain)`, with an absolute error below `3e-5` dB.
+/// Same conditions as [`log2`], see [`gain_to_db_floored`] for a variant
+/// with defined results for non-positive gains.
+#[inline]
+pub fn gain_to_db<const N: usize>(gain: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    const RATIO: f32 = 20. * core::f32::consts::LOG10_2;
+    log2(gain) * Simd::splat(RATIO)
+}
+
+/// Like [`gain_to_db`], but returns `max(gain_to_db(gain), min_db)`, and
+/// `min_db` for lanes where `gain` is zero, negative, or `NAN`.
+#[inline]
+pub fn gain_to_db_floored<const N: usize>(gain: Simd<f32, N>, min_db: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let db = gain_to_db(gain).simd_max(min_db);
+    gain.simd_gt(Simd::splat(0.)).select(db, min_db)
+}
+
+#[inline]
+pub fn flp_to_fxp<const N: usize>(x: Simd<f32, N>) -> Simd<u32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    const RATIO: f32 = (1u64 << u32::BITS) as f32;
+    unsafe { (x * Simd::splat(RATIO)).to_int_unchecked() }
+}
+
+#[inline]
+pub fn fxp_to_flp<const N: usize>(x: Simd<u32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    const RATIO: f32 = 1. / (1u64 << u32::BITS) as f32;
+    x.cast() * Simd::splat(RATIO)
+}
+
+/// Advances the fixed-point phases `phase` by `inc`, wrapping around, and returns the new
+/// phases, along with a mask of the lanes that wrapped around (e.g. for hard sync)
+#[inline]
+pub fn fxp_phase_advance<const N: usize>(
+    phase: Simd<u32, N>,
+    inc: Simd<u32, N>,
+) -> (Simd<u32, N>, TMask<N>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let next = phase + inc;
+    (next, next.simd_lt(phase))
+}
+
+/// Splits the fixed-point phases `phase` into an index into a table of `2^table_len_log2`
+/// elements, and the (floating-point) fractional position between that index and the next,
+/// in `[0 ; 1)`, with 24 bits of precision. `table_len_log2` must be in `[1 ; 31]`.
+#[inline]
+pub fn fxp_to_table_index<const N: usize>(
+    phase: Simd<u32, N>,
+    table_len_log2: u32,
+) -> (Simd<u32, N>, Simd<f32, N>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    debug_assert!((1..u32::BITS).contains(&table_len_log2));
+
+    const FRAC_BITS: u32 = f32::MANTISSA_DIGITS;
+    const RATIO: f32 = 1. / (1 << FRAC_BITS) as f32;
+
+    let index = phase >> (u32::BITS - table_len_log2);
+    let frac = (phase << table_len_log2) >> (u32::BITS - FRAC_BITS);
+
+    (index, frac.cast() * Simd::splat(RATIO))
+}
+
+/// Lane types with implementations of some of the approximations of this module, for code
+/// that is generic over `f32` and `f64` vectors. The free functions are the `f32` versions.
+pub trait SimdMathElement: SimdElement {
+    /// See [`lerp`]
+    fn lerp<const N: usize>(a: Simd<Self, N>, b: Simd<Self, N>, t: Simd<Self, N>) -> Simd<Self, N>
+    where
+        LaneCount<N>: SupportedLaneCount;
+
+    /// See [`exp2`]
+    ///
+    /// # Safety
+    ///
+    /// `v` must be non-NAN, finite, and fit in the integer type of the same size as `Self`
+    unsafe fn exp2<const N: usize>(v: Simd<Self, N>) -> Simd<Self, N>
+    where
+        LaneCount<N>: SupportedLaneCount;
+
+    /// See [`log2`]
+    fn log2<const N: usize>(v: Simd<Self, N>) -> Simd<Self, N>
+    where
+        LaneCount<N>: SupportedLaneCount;
+
+    /// See [`tan_half_x`]
+    fn tan_half_x<const N: usize>(x: Simd<Self, N>) -> Simd<Self, N>
+    where
+        LaneCount<N>: SupportedLaneCount;
+
+    /// See [`pow`]
+    ///
+    /// # Safety
+    ///
+    /// Same conditions as [`Self::exp2`]
+    unsafe fn pow<const N: usize>(base: Simd<Self, N>, exp: Simd<Self, N>) -> Simd<Self, N>
+    where
+        LaneCount<N>: SupportedLaneCount;
+}
+
+impl SimdMathElement for f32 {
+    #[inline]
+    fn lerp<const N: usize>(a: Simd<f32, N>, b: Simd<f32, N>, t: Simd<f32, N>) -> Simd<f32, N>
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        lerp(a, b, t)
+    }
+
+    #[inline]
+    unsafe fn exp2<const N: usize>(v: Simd<f32, N>) -> Simd<f32, N>
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        exp2(v)
+    }
+
+    #[inline]
+    fn log2<const N: usize>(v: Simd<f32, N>) -> Simd<f32, N>
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        log2(v)
+    }
+
+    #[inline]
+    fn tan_half_x<const N: usize>(x: Simd<f32, N>) -> Simd<f32, N>
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        tan_half_x(x)
+    }
+
+    #[inline]
+    unsafe fn pow<const N: usize>(base: Simd<f32, N>, exp: Simd<f32, N>) -> Simd<f32, N>
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        pow(base, exp)
+    }
+}
+
+const F64_MANTISSA_BITS: u64 = f64::MANTISSA_DIGITS as u64 - 1;
+const F64_ONE_BITS: u64 = 1f64.to_bits();
+
+/// The `f64` versions are tuned for double precision: `exp2` has a relative error below
+/// `1e-14` in `[-1022 ; 1023]` (unspecified results outside of it), `log2` an absolute error
+/// below `2e-15`, and `tan_half_x` a relative error below `1e-12` for `|x| < 3.14`.
+impl SimdMathElement for f64 {
+    #[inline]
+    fn lerp<const N: usize>(a: Simd<f64, N>, b: Simd<f64, N>, t: Simd<f64, N>) -> Simd<f64, N>
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        t.mul_add(b - a, a)
+    }
+
+    #[inline]
+    unsafe fn exp2<const N: usize>(v: Simd<f64, N>) -> Simd<f64, N>
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        // LN_2^n / n!
+        // constants
+        let coeffs = [
+            core::f64::consts::LN_2,
+            0.240_226_506_959_100_7,
+            0.055_504_108_664_821_58,
+            0.009_618_129_107_628_477,
+            0.001_333_355_814_642_844,
+            0.000_154_035_303_933_816_06,
+            0.000_015_252_733_804_059_838,
+            0.000_001_321_548_679_014_430_5,
+            0.000_000_101_780_860_092_396_96,
+            0.000_000_007_054_911_620_801_121,
+            0.000_000_000_444_553_827_187_081,
+        ];
+
+        let rounded = map(v, f64::round_ties_even);
+
+        let int_bits: Simd<u64, N> = unsafe { rounded.to_int_unchecked::<i64>() }.cast();
+        let int = Simd::from_bits((int_bits << F64_MANTISSA_BITS) + Simd::splat(F64_ONE_BITS));
+
+        let x = v - rounded; // is always in [-0.5 ; 0.5]
+
+        let y = poly_horner(x, coeffs);
+        int.mul_add(x * y, int)
+    }
+
+    #[inline]
+    fn log2<const N: usize>(v: Simd<f64, N>) -> Simd<f64, N>
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        const EXP_BIAS: i64 = (F64_ONE_BITS >> F64_MANTISSA_BITS) as i64;
+
+        // 2 / (ln(2) * (2n + 1))
+        // constants
+        let coeffs =
+            [1., 3., 5., 7., 9., 11., 13., 15., 17.].map(|n| 2. * core::f64::consts::LOG2_E / n);
+        let one = Simd::splat(1.);
+
+        // like normalize_subnormals, scale subnormal (and zero) lanes by 2^52,
+        // so that their exponent bits become meaningful
+        let subnormal = v.to_bits().simd_lt(Simd::splat(1 << F64_MANTISSA_BITS));
+        let v = subnormal.select(v * Simd::splat((1u64 << F64_MANTISSA_BITS) as f64), v);
+        let offset = subnormal.select(Simd::splat(F64_MANTISSA_BITS as f64), Simd::splat(0.));
+
+        let bits = v.to_bits();
+        let exponent = ((bits >> F64_MANTISSA_BITS).cast::<i64>() - Simd::splat(EXP_BIAS)).cast();
+        let exponent: Simd<f64, N> = exponent - offset;
+        let x = Simd::<f64, N>::from_bits(
+            bits & Simd::splat((1 << F64_MANTISSA_BITS) - 1) | Simd::splat(F64_ONE_BITS),
+        );
+
+        // bring x into [sqrt(1/2) ; sqrt(2)), to minimize |s| below
+        let big = x.simd_gt(Simd::splat(core::f64::consts::SQRT_2));
+        let x = big.select(x * Simd::splat(0.5), x);
+        let exponent = big.select(exponent + one, exponent);
+
+        // log2(x) = 2 * atanh(s) / ln(2), where s = (x - 1) / (x + 1)
+        let s = (x - one) / (x + one);
+        s.mul_add(poly_horner(s * s, coeffs), exponent)
+    }
+
+    #[inline]
+    fn tan_half_x<const N: usize>(x: Simd<f64, N>) -> Simd<f64, N>
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        // truncation of Lambert's continued fraction of tan
+        // constants
+        let num_coeffs = [
+            1.,
+            -0.035_714_285_714_285_71,
+            0.000_292_397_660_818_713_46,
+            -0.000_000_767_851_000_049_142_4,
+            0.000_000_000_609_405_555_594_557_5,
+            -0.000_000_000_000_071_026_288_530_834_21,
+        ];
+        let den_coeffs = [
+            2.,
+            -0.238_095_238_095_238_08,
+            0.003_759_398_496_240_601_3,
+            -0.000_017_199_862_401_100_79,
+            0.000_000_025_595_033_334_971_417,
+            -0.000_000_000_009_375_470_086_070_115,
+        ];
+
+        let x2 = x * x;
+        let xden = x / poly_horner(x2, den_coeffs);
+
+        poly_horner(x2, num_coeffs) * xden
+    }
+
+    #[inline]
+    unsafe fn pow<const N: usize>(base: Simd<f64, N>, exp: Simd<f64, N>) -> Simd<f64, N>
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        <Self as SimdMathElement>::exp2(<Self as SimdMathElement>::log2(base) * exp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Spacing between `x` and the next `f32` away from zero
+    fn ulp(x: f32) -> f64 {
+        let x = x.abs();
+        (f32::from_bits(x.to_bits() + 1) - x) as f64
+    }
+
+    fn exp2_scalar(v: f32) -> f32 {
+        let y = unsafe { exp2(Simd::<f32, 1>::splat(v)) };
+        y[0]
+    }
+
+    fn log2_scalar(v: f32) -> f32 {
+        log2(Simd::<f32, 1>::splat(v))[0]
+    }
+
+    fn exp2_ulp_error(v: f32) -> f64 {
+        let exact = (v as f64).exp2();
+        (exp2_scalar(v) as f64 - exact).abs() / ulp(exact as f32)
+    }
+
+    // log2 has an absolute, rather than relative, error bound, so outputs
+    // smaller than 1 in magnitude are measured in ULPs of 1 instead
+    fn log2_ulp_error(v: f32) -> f64 {
+        let exact = (v as f64).log2();
+        (log2_scalar(v) as f64 - exact).abs() / ulp((exact as f32).abs().max(1.))
+    }
+
+    fn max_error(inputs: impl IntoIterator<Item = f32>, error: impl Fn(f32) -> f64) -> f64 {
+        inputs.into_iter().map(error).fold(0., f64::max)
+    }
+
+    const EXP2_MAX_ULP: f64 = 40.;
+    const LOG2_MAX_ULP: f64 = 20.;
+
+    #[test]
+    fn polynomial_evaluation() {
+        let x = Simd::<f32, 4>::from_array([-1.5, -0.25, 0.5, 2.]);
+        let coeffs = [0.5, -1., 0.25, 2., -0.75, 0.125, 1.];
+        let expected = map(x, |x| coeffs.iter().rev().fold(0., |acc, &c| acc * x + c));
+
+        assert_eq!(poly_horner(x, coeffs), expected);
+        assert!((poly_estrin(x, coeffs) - expected)
+            .abs()
+            .simd_lt(Simd::splat(1e-5))
+            .all());
+        assert_eq!(poly_horner(x, []), Simd::splat(0.));
+        assert_eq!(poly_estrin(x, [3.]), Simd::splat(3.));
+    }
+
+    /// Outputs of the hand-written `mul_add` chains these functions used before
+    /// switching to `poly_horner`, which must be bit-identical
+    #[test]
+    fn poly_horner_pinned_outputs() {
+        let grid = |a: f32, b: f32| {
+            Simd::<f32, 16>::from_array(core::array::from_fn(|i| a + (b - a) * i as f32 / 15.))
+        };
+
+        let tan_half_x_bits = [
+            0xc1619996, 0xc066887a, 0xbffb7d36, 0xbfa14cdc, 0xbf57a035, 0xbf0bda7b, 0xbe9e6151,
+            0xbdcd7c46, 0x3dcd7c46, 0x3e9e6151, 0x3f0bda7b, 0x3f57a036, 0x3fa14cde, 0x3ffb7d31,
+            0x4066887a, 0x41619996,
+        ];
+        let exp2_bits = [
+            0x35800000, 0x36cb2fec, 0x3821451b, 0x39800000, 0x3acb2ff5, 0x3c214514, 0x3d800000,
+            0x3ecb2fec, 0x4021451b, 0x41800000, 0x42cb2fec, 0x4421451b, 0x45800000, 0x46cb2ffe,
+            0x4821450d, 0x49800000,
+        ];
+        let log2_bits = [
+            0xc0549a6f, 0xbf3ca9a7, 0x3e0ccddb, 0x3f2d9644, 0x3f890274, 0x3fb07306, 0x3fd0ee39,
+            0x3fec8b17, 0x400247a1, 0x400ce79a, 0x40166e8a, 0x401f1133, 0x4026f6c0, 0x402e3ce9,
+            0x4034faf4, 0x403b4394,
+        ];
+
+        assert_eq!(
+            tan_half_x(grid(-3., 3.)).to_bits().to_array(),
+            tan_half_x_bits
+        );
+        assert_eq!(
+            unsafe { exp2(grid(-20., 20.)) }.to_bits().to_array(),
+            exp2_bits
+        );
+        assert_eq!(log2(grid(0.1, 7.6)).to_bits().to_array(), log2_bits);
+    }
+
+    #[test]
+    fn f64_exp2_log2_accuracy() {
+        for i in -100_000..=100_000 {
+            let v = Simd::<f64, 1>::splat(i as f64 * 0.010_007);
+            let exp2 = unsafe { <f64 as SimdMathElement>::exp2(v) }[0];
+            assert!((exp2 / v[0].exp2() - 1.).abs() < 1e-12);
+
+            let x = Simd::<f64, 1>::splat((i as f64 * 0.001_3).exp2());
+            let log2 = <f64 as SimdMathElement>::log2(x)[0];
+            assert!((log2 - x[0].log2()).abs() < 1e-12);
+        }
+
+        // subnormals, the smallest one included
+        for i in 1..=1000 {
+            let x = Simd::<f64, 1>::splat(f64::from_bits(i * 4_503_599_627_370));
+            let log2 = <f64 as SimdMathElement>::log2(x)[0];
+            assert!((log2 - x[0].log2()).abs() < 1e-12, "{:e}", x[0]);
+        }
+
+        let x = Simd::<f64, 1>::splat(f64::from_bits(1));
+        assert!((<f64 as SimdMathElement>::log2(x)[0] + 1074.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn atan2_quadrants_and_axes() {
+        // every quadrant, then the four axis directions
+        let y = Simd::<f32, 8>::from_array([1., 2., -0.5, -3., 0., 1., 0., -1.]);
+        let x = Simd::from_array([2., -1., -0.5, 4., 1., 0., -1., 0.]);
+        let expected = Simd::from_array(core::array::from_fn(|i| y[i].atan2(x[i])));
+
+        assert!((atan2(y, x) - expected)
+            .abs()
+            .simd_lt(Simd::splat(3e-6))
+            .all());
+
+        let y = Simd::from_array([0., -0., 0., -0.]);
+        let x = Simd::from_array([0., 0., -0., -0.]);
+        let expected = [0., -0., core::f32::consts::PI, -core::f32::consts::PI];
+        assert_eq!(
+            atan2(y, x).to_array().map(f32::to_bits),
+            expected.map(f32::to_bits)
+        );
+    }
+
+    #[test]
+    fn hypot_extremes() {
+        let tiny = f32::from_bits(3);
+        let x = Simd::from_array([f32::MAX, 2e38, 3e-39, tiny, 3., 0., f32::INFINITY, f32::NAN]);
+        let y = Simd::from_array([f32::MAX, -2e38, 4e-39, tiny, -4., -0., f32::NAN, 1.]);
+        let h = hypot(x, y).to_array();
+
+        for i in 1..6 {
+            let expected = (x[i] as f64).hypot(y[i] as f64);
+            let tolerance = ulp(expected as f32).max(tiny as f64) * 2.;
+            assert!((h[i] as f64 - expected).abs() <= tolerance);
+        }
+
+        // the exact result doesn't fit in an f32
+        assert_eq!(h[0], f32::INFINITY);
+        assert_eq!(h[6], f32::INFINITY);
+        assert!(h[7].is_nan());
+    }
+
+    #[test]
+    fn cbrt_accuracy() {
+        let x = Simd::from_array([-27., 0., -0., 1e-40, -3e-39, f32::INFINITY, -8e30, 0.001]);
+        let y = cbrt(x).to_array();
+
+        assert_eq!([y[1].to_bits(), y[2].to_bits()], [0, (-0f32).to_bits()]);
+        assert_eq!(y[5], f32::INFINITY);
+
+        for i in [0, 3, 4, 6, 7] {
+            assert!((y[i] / x[i].cbrt() - 1.).abs() < 1.5e-6);
+        }
+
+        for i in -10_000..=10_000 {
+            let x = Simd::<f32, 1>::splat(i as f32 * 0.0173);
+            assert!((cbrt(x)[0] - x[0].cbrt()).abs() <= x[0].cbrt().abs() * 1.5e-6);
+        }
+    }
+
+    #[test]
+    fn hz_to_w_c_above_nyquist() {
+        let w_c = hz_to_w_c(Simd::from_array([0., 1000., 22_050., 30_000.]), 44_100.);
+
+        assert!((w_c[1] - 0.142_476_1).abs() < 1e-6);
+        assert_eq!(w_c[2], w_c[3]);
+        assert!(w_c[3] < core::f32::consts::PI);
+        assert!(tan_half_x(w_c).to_array().iter().all(|g| g.is_finite()));
+    }
+
+    #[test]
+    fn powf_signed_zero_and_negative_bases() {
+        let base = Simd::from_array([-2., 0., -2., 3., -0., 0., -2., 2.]);
+        let exp = Simd::from_array([3., 2., 2., 2., 0., -1., 0.5, -1.]);
+        let y = unsafe { powf_signed(base, exp) }.to_array();
+
+        let expected = [-8., 0., 4., 9., 1., f32::INFINITY];
+        for (&y, expected) in y.iter().zip(expected) {
+            assert!((y - expected).abs() <= expected.abs() * 1e-5 || y == expected);
+        }
+
+        assert!(y[6].is_nan());
+        assert!((y[7] - 0.5).abs() < 1e-5);
+
+        let exp = Simd::from_array([f32::INFINITY, f32::NEG_INFINITY, f32::NAN, 0.]);
+        let y = unsafe { powf_signed(Simd::from_array([0., -0., 0., 0.]), exp) }.to_array();
+
+        assert_eq!([y[0], y[1], y[3]], [0., f32::INFINITY, 1.]);
+        assert!(y[2].is_nan());
+    }
+
+    #[test]
+    fn recip_fast_accuracy() {
+        let mut max = 0f64;
+
+        for i in 0..=20_000 {
+            let x = (i as f32 * 0.004 - 40.).exp2();
+            for x in [x, -x] {
+                let y = recip_fast(Simd::<f32, 1>::splat(x))[0];
+                max = max.max((y as f64 * x as f64 - 1.).abs());
+            }
+        }
+
+        println!("recip_fast: max relative error {max:e}");
+        assert!(max < 3e-7);
+    }
+
+    #[test]
+    fn sinh_cosh_accuracy() {
+        let inputs = (0..=10_000)
+            .map(|i| i as f32 * 0.008 - 40.)
+            .chain([1e-6, -1e-6, 1e-3, 0.999_99, 1.]);
+
+        for x in inputs {
+            let v = Simd::<f32, 1>::splat(x);
+            let (sinh, cosh) = unsafe { (sinh(v)[0] as f64, cosh(v)[0] as f64) };
+            let x = x as f64;
+
+            assert!((sinh - x.sinh()).abs() <= x.sinh().abs() * 5e-6);
+            assert!((cosh / x.cosh() - 1.).abs() < 5e-6);
+        }
+    }
+
+    #[test]
+    fn exp2_grid() {
+        let max = max_error((0..=20_000).map(|i| i as f32 * 0.001 - 10.), exp2_ulp_error);
+        println!("exp2, [-10 ; 10]: max error {max} ULPs");
+        assert!(max <= EXP2_MAX_ULP);
+    }
+
+    #[test]
+    fn exp2_precise_fractional_domain() {
+        let exp2_precise_ulp_error = |v: f32| {
+            let exact = (v as f64).exp2();
+            let y = unsafe { exp2_precise(Simd::<f32, 1>::splat(v)) };
+            (y[0] as f64 - exact).abs() / ulp(exact as f32)
+        };
+
+        let inputs = || (-1_000_000..=1_000_000).map(|i| i as f32 * 1e-6);
+        let max = max_error(inputs(), exp2_ulp_error);
+        let max_precise = max_error(inputs(), exp2_precise_ulp_error);
+        println!("[-1 ; 1]: exp2 max error {max} ULPs, exp2_precise: {max_precise} ULPs");
+
+        assert!(max <= EXP2_MAX_ULP);
+        assert!(max_precise <= 2.);
+        assert!(
+            max_error(
+                (0..=20_000).map(|i| i as f32 * 0.01 - 100.),
+                exp2_precise_ulp_error
+            ) <= 2.
+        );
+    }
+
+    #[test]
+    fn exp2_integers() {
+        for i in -126..=127 {
+            assert_eq!(exp2_scalar(i as f32), 2f32.powi(i));
+        }
+    }
+
+    #[test]
+    fn exp2_rounding_boundaries() {
+        let inputs = (-20..20).flat_map(|i| {
+            let half = i as f32 + 0.5;
+            [half.next_down(), half, half.next_up()]
+        });
+
+        let max = max_error(inputs, exp2_ulp_error);
+        println!("exp2, around x.5: max error {max} ULPs");
+        assert!(max <= EXP2_MAX_ULP);
+    }
+
+    #[test]
+    fn exp2_subnormal_outputs() {
+        // `exp2` rounds `v` to -126, so outputs
+        // in this range are scaled down from 2^-126
+        let max = max_error(
+            (0..=5_000).map(|i| i as f32 * -0.0001 - 126.),
+            exp2_ulp_error,
+        );
+        println!("exp2, [-126.5 ; -126]: max error {max} ULPs");
+        assert!(max <= EXP2_MAX_ULP);
+    }
+
+    #[test]
+    fn sign_bit_helpers() {
+        let nan = f32::from_bits(0x7fc0_1234);
+        let x = Simd::from_array([-0., 0., -2.5, 3., -nan, nan, f32::NEG_INFINITY, 1e-40]);
+        let bits = |v: Simd<f32, 8>| v.to_bits().to_array();
+
+        assert_eq!(bits(abs_bits(x)), bits(x).map(|b| b & !SIGN_BIT));
+        assert_eq!(bits(copysign(abs_bits(x), x)), bits(x));
+        assert_eq!(
+            bits(flip_sign(x, Simd::splat(-0.))),
+            bits(x).map(|b| b ^ SIGN_BIT)
+        );
+        assert_eq!(bits(flip_sign(x, Simd::splat(f32::NAN))), bits(x));
+        assert_eq!(bits(flip_sign(x, x)), bits(abs_bits(x)));
+        assert_eq!(
+            bits(copysign(Simd::splat(1.), x)),
+            bits(map(x, |x| 1f32.copysign(x)))
+        );
+
+        let signum = signum_or_zero(x).to_array();
+        assert_eq!(
+            signum.map(f32::to_bits)[..4],
+            [(-0f32).to_bits(), 0, (-1f32).to_bits(), 1f32.to_bits()]
+        );
+        assert_eq!(signum[4].to_bits(), (-nan).to_bits());
+        assert_eq!(signum[5].to_bits(), nan.to_bits());
+        assert_eq!(signum[6..], [-1., 1.]);
+    }
+
+    #[test]
+    fn round_ties_to_even() {
+        let ties = Simd::from_array([0.5, 1.5, 2.5, -0.5, -1.5, -2.5, 4_194_304.5, 8_388_607.5]);
+        let expected = [0., 2., 2., -0., -2., -2., 4_194_304., 8_388_608.];
+
+        assert_eq!(
+            round_ties_even(ties).to_array().map(f32::to_bits),
+            expected.map(f32::to_bits)
+        );
+        assert_eq!(
+            unsafe { round_to_int_unchecked(ties) },
+            Simd::from_array(expected.map(|x| x as i32)),
+        );
+
+        let special = Simd::from_array([f32::INFINITY, -1e30, 16_777_217., -0.]);
+        assert_eq!(round_ties_even(special), special);
+        assert!(round_ties_even(Simd::<f32, 1>::splat(f32::NAN))[0].is_nan());
+
+        for i in -100_000..=100_000 {
+            let x = Simd::<f32, 1>::splat(i as f32 * 0.013);
+            assert_eq!(round_ties_even(x)[0], x[0].round_ties_even());
+        }
+    }
+
+    #[test]
+    fn fxp_phase_accumulator() {
+        let inc = Simd::<u32, 2>::from_array([0x1234_5679, 0xfedc_ba98]);
+        let ratio = inc.to_array().map(|inc| inc as f64 / (1u64 << 32) as f64);
+        let mut phase = Simd::splat(0);
+
+        for n in 1..=1_000_000 {
+            let wrapped;
+            (phase, wrapped) = fxp_phase_advance(phase, inc);
+            let (index, frac) = fxp_to_table_index(phase, 10);
+
+            for i in 0..2 {
+                let expected = n as f64 * ratio[i];
+                assert_eq!(
+                    wrapped.test(i),
+                    expected.floor() > (expected - ratio[i]).floor()
+                );
+
+                let expected = expected.fract();
+                assert!((fxp_to_flp(phase)[i] as f64 - expected).abs() < 1e-6);
+                assert!(((index[i] as f64 + frac[i] as f64) / 1024. - expected).abs() < 1e-6);
+                assert!(frac[i] < 1.);
+            }
+        }
+    }
+
+    #[test]
+    fn phase_wrapping() {
+        let x = Simd::from_array([1., -1., 0.5, -0.5, -1e-9, 3.25, -3.25, 0.]);
+        assert_eq!(
+            wrap01(x),
+            Simd::from_array([0., 0., 0.5, 0.5, 0., 0.25, 0.75, 0.])
+        );
+        assert_eq!(
+            wrap_signed(x),
+            Simd::from_array([0., 0., -0.5, -0.5, -1e-9, 0.25, -0.25, 0.]),
+        );
+
+        let inc = Simd::<f32, 2>::from_array([
+            core::f32::consts::SQRT_2 - 1.,
+            -0.1 * core::f32::consts::E,
+        ]);
+        let mut phase = Simd::splat(0.);
+        let mut signed_phase = Simd::splat(0.);
+        for _ in 0..1_000_000 {
+            phase = wrap01(phase + inc);
+            signed_phase = wrap_signed(signed_phase + inc);
+
+            assert!(phase.simd_ge(Simd::splat(0.)).all() && phase.simd_lt(Simd::splat(1.)).all());
+            assert!(signed_phase.abs().simd_le(Simd::splat(0.5)).all());
+            assert!(signed_phase.simd_ne(Simd::splat(0.5)).all());
+        }
+    }
+
+    #[test]
+    fn rem_euclid_signs() {
+        let x = Simd::from_array([5., -5., 5., -5., -0.25, 1000.5, -1000.5, 3.]);
+        let m = Simd::from_array([3., 3., -3., -3., 1., 2., 2., 3.]);
+        assert_eq!(
+            rem_euclid(x, m),
+            Simd::from_array([2., 1., 2., 1., 0.75, 0.5, 1.5, 0.])
+        );
+
+        for m in [1., 0.75, -2.5, 7.] {
+            for i in -10_000..=10_000 {
+                let x = i as f32 * 0.37;
+                let r = rem_euclid(Simd::<f32, 1>::splat(x), Simd::splat(m))[0];
+                let diff = (r - x.rem_euclid(m)).abs();
+
+                assert!(r >= 0. && r < m.abs());
+                assert!(diff.min(m.abs() - diff) < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn fold_reflections() {
+        let threshold = Simd::<f32, 4>::from_array([1., 0.5, 2., 0.25]);
+
+        let x = Simd::from_array([0.3, -0.5, -1.75, 0.]);
+        assert_eq!(fold(x, threshold), x);
+
+        let past = Simd::splat(1. / 64.);
+        assert_eq!(fold(threshold + past, threshold), threshold - past);
+        assert_eq!(fold(-threshold - past, threshold), past - threshold);
+
+        for i in -100..=100 {
+            let x = threshold * Simd::splat(i as f32 * 0.1);
+            let expected = threshold * Simd::splat(fold_scalar(i as f32 * 0.1));
+            assert!((fold(x, threshold) - expected)
+                .abs()
+                .simd_lt(Simd::splat(1e-5))
+                .all());
+        }
+
+        // reflects `x` (with a threshold of 1) until it's in range
+        fn fold_scalar(mut x: f32) -> f32 {
+            while x.abs() > 1. {
+                x = x.signum() * 2. - x;
+            }
+            x
+        }
+    }
+
+    #[test]
+    fn cubic_interp_quadratic() {
+        let f = |x: f32| 0.75 * x * x - 2. * x + 0.5;
+        let y = [-1., 0., 1., 2.].map(|x| Simd::<f32, 1>::splat(f(x)));
+
+        for i in 0..=16 {
+            let t = i as f32 / 16.;
+            let interp = cubic_interp(y[0], y[1], y[2], y[3], Simd::splat(t));
+            assert!((interp[0] - f(t)).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn lagrange_interp_cubic() {
+        let f = |x: f32| ((0.25 * x - 1.) * x + 0.5) * x - 2.;
+        let y = [-1., 0., 1., 2.].map(|x| Simd::<f32, 1>::splat(f(x)));
+
+        for i in 0..=16 {
+            let t = i as f32 / 16.;
+            let interp = lagrange_interp(y[0], y[1], y[2], y[3], Simd::splat(t));
+            assert!((interp[0] - f(t)).abs() < 1e-6);
+        }
+    }
+
+    /// Per-lane sample sequences, irregular enough to have no simple structure
+    fn interp_samples() -> Vec<Simd<f32, 4>> {
+        (0..32)
+            .map(|k| {
+                Simd::from_array(
+                    [0, 1, 2, 3].map(|lane| ((k * 37 + lane * 11) % 17) as f32 / 8. - 1.),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lagrange_interp_low_degree() {
+        let polynomials: [fn(f32) -> f32; 3] =
+            [|_| -1.25, |x| 0.5 * x - 3., |x| (0.75 * x - 2.) * x + 0.5];
+
+        for f in polynomials {
+            let y = [-1., 0., 1., 2.].map(|x| Simd::<f32, 1>::splat(f(x)));
+
+            for i in 0..=16 {
+                let t = i as f32 / 16.;
+                let interp = lagrange_interp(y[0], y[1], y[2], y[3], Simd::splat(t));
+                assert!((interp[0] - f(t)).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn lagrange_interp_against_scalar() {
+        // sum of the Lagrange basis polynomials, with nodes at -1, 0, 1 and 2
+        fn reference(y: [f32; 4], t: f64) -> f64 {
+            let nodes = [-1., 0., 1., 2.];
+            let basis = |i: usize| {
+                (0..4)
+                    .filter(|&j| j != i)
+                    .map(|j| (t - nodes[j]) / (nodes[i] - nodes[j]))
+                    .product::<f64>()
+            };
+            (0..4).map(|i| y[i] as f64 * basis(i)).sum()
+        }
+
+        let x = interp_samples();
+        for y in x.windows(4) {
+            let y: [_; 4] = y.try_into().unwrap();
+
+            for i in 0..=10 {
+                let t = i as f32 / 10.;
+                let interp = lagrange_interp(y[0], y[1], y[2], y[3], Simd::splat(t));
+
+                for lane in 0..4 {
+                    let expected = reference(y.map(|y| y[lane]), t as f64);
+                    assert!((interp[lane] as f64 - expected).abs() < 1e-6);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cubic_interp_against_scalar() {
+        // textbook Catmull-Rom spline, in matrix form
+        fn reference([y0, y1, y2, y3]: [f32; 4], t: f64) -> f64 {
+            let [y0, y1, y2, y3] = [y0, y1, y2, y3].map(f64::from);
+            let c1 = y2 - y0;
+            let c2 = 2. * y0 - 5. * y1 + 4. * y2 - y3;
+            let c3 = -y0 + 3. * y1 - 3. * y2 + y3;
+            0.5 * (2. * y1 + t * (c1 + t * (c2 + t * c3)))
+        }
+
+        let x = interp_samples();
+        for y in x.windows(4) {
+            let y: [_; 4] = y.try_into().unwrap();
+
+            for i in 0..=10 {
+                let t = i as f32 / 10.;
+                let interp = cubic_interp(y[0], y[1], y[2], y[3], Simd::splat(t));
+
+                for lane in 0..4 {
+                    let expected = reference(y.map(|y| y[lane]), t as f64);
+                    assert!((interp[lane] as f64 - expected).abs() < 1e-6);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cubic_interp_derivative_continuity() {
+        let h = 1e-3;
+        let x = interp_samples();
+
+        // the segment between x[k + 1] and x[k + 2], at t
+        let segment =
+            |k: usize, t: f32| cubic_interp(x[k], x[k + 1], x[k + 2], x[k + 3], Simd::splat(t));
+
+        for k in 0..x.len() - 4 {
+            // one-sided finite differences, on both sides of x[k + 2]
+            let left = (segment(k, 1.) - segment(k, 1. - h)) / Simd::splat(h);
+            let right = (segment(k + 1, h) - segment(k + 1, 0.)) / Simd::splat(h);
+
+            // both are within O(h) of the derivative at x[k + 2]
+            let diff = (left - right).abs().reduce_max();
+            assert!(diff < 2e-2, "segment {k}: {left:?} vs {right:?}");
+            assert_eq!(segment(k, 1.), segment(k + 1, 0.));
+        }
+    }
+
+    #[test]
+    fn smoothstep_edges() {
+        let edge0 = Simd::from_array([0., 1., 2., -1.]);
+        let edge1 = Simd::from_array([1., 1., 4., -1.]);
+
+        for f in [smoothstep, smootherstep] {
+            assert_eq!(
+                f(edge0, edge1, edge0 - Simd::splat(1.)),
+                Simd::from_array([0.; 4])
+            );
+            assert_eq!(f(edge0, edge1, edge0), Simd::from_array([0., 1., 0., 1.]));
+            assert_eq!(f(edge0, edge1, edge1), Simd::from_array([1.; 4]));
+            assert_eq!(
+                f(edge0, edge1, edge1 + Simd::splat(1.)),
+                Simd::from_array([1.; 4])
+            );
+
+            let mid = f(edge0, edge1, (edge0 + edge1) * Simd::splat(0.5));
+            assert_eq!(mid, Simd::from_array([0.5, 1., 0.5, 1.]));
+        }
+    }
+
+    #[test]
+    fn smoothstep_reversed_edges() {
+        let (zero, one) = (Simd::<f32, 1>::splat(0.), Simd::splat(1.));
+
+        for f in [smoothstep, smootherstep] {
+            for i in -16..=32 {
+                let x = Simd::splat(i as f32 / 16.);
+                let fade_out = f(one, zero, x);
+
+                assert_eq!(fade_out, one - f(zero, one, x));
+                assert!(fade_out[0] >= 0. && fade_out[0] <= 1.);
+            }
+        }
+    }
+
+    #[test]
+    fn db_gain_round_trip() {
+        for i in -600..=600 {
+            let db = Simd::<f32, 1>::splat(i as f32 * 0.1);
+            let gain = unsafe { db_to_gain(db) };
+
+            assert!((gain[0] as f64 / 10f64.powf(db[0] as f64 / 20.) - 1.).abs() < 5e-6);
+            assert!((gain_to_db(gain) - db).abs()[0] < 1e-4);
+        }
+
+        let gain = Simd::from_array([0., -1., f32::NAN, 1e-10]);
+        let min_db = Simd::splat(-120.);
+        assert_eq!(gain_to_db_floored(gain, min_db), min_db);
+    }
+
+    #[test]
+    fn asinh_clip_shape() {
+        for drive in [1e-4, 0.1, 1., 10.] {
+            let drive = Simd::<f32, 1>::splat(drive);
+            assert_eq!(asinh_clip(Simd::splat(1.), drive)[0], 1.);
+
+            let mut prev = f32::NEG_INFINITY;
+            for i in 0..=4000 {
+                let x = Simd::splat(i as f32 * 0.001 - 2.);
+                let y = asinh_clip(x, drive);
+
+                assert_eq!(asinh_clip(-x, drive), -y);
+                assert!(y[0] >= prev);
+                prev = y[0];
+
+                if drive[0] < 1e-3 {
+                    assert!((y - x).abs()[0] < 1e-5);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sin_cos_error_bounds() {
+        let abs_error = |y: f32, exact: f64| (y as f64 - exact).abs();
+
+        let inputs = || (-1_000_000..=1_000_000).map(|i| i as f32 * 0.001);
+        let max_sin = max_error(inputs(), |x| {
+            abs_error(sin(Simd::<f32, 1>::splat(x))[0], (x as f64).sin())
+        });
+        let max_cos = max_error(inputs(), |x| {
+            abs_error(cos(Simd::<f32, 1>::splat(x))[0], (x as f64).cos())
+        });
+        println!("[-1000 ; 1000]: sin max error {max_sin:e}, cos: {max_cos:e}");
+        assert!(max_sin < 8e-7 && max_cos < 8e-7);
+
+        let max_sin_cos = max_error(
+            (-1_000_000..=1_000_000).map(|i| i as f32 * core::f32::consts::PI * 1e-6),
+            |x| {
+                let (sin, cos) = sin_cos(Simd::<f32, 1>::splat(x));
+                let x = x as f64;
+                abs_error(sin[0], x.sin()).max(abs_error(cos[0], x.cos()))
+            },
+        );
+        println!("[-pi ; pi]: sin_cos max error {max_sin_cos:e}");
+        assert!(max_sin_cos < 1e-7);
+    }
+
+    #[test]
+    fn sin_cos_sweep() {
+        // two full periods, 4096 points
+        for i in 0..4096 {
+            let x = (i as f32 / 1024. - 2.) * core::f32::consts::PI;
+            let v = Simd::<f32, 1>::splat(x);
+
+            assert!((sin(v)[0] - x.sin()).abs() < 1e-6);
+            assert!((cos(v)[0] - x.cos()).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn sin_cos_consistency() {
+        for i in -100_000..=100_000 {
+            let x = Simd::<f32, 1>::splat(i as f32 * 0.001);
+            let (s, c) = sin_cos(x);
+
+            assert!((s * s + c * c - Simd::splat(1.)).abs()[0] < 1e-6);
+            assert!((s - sin(x)).abs()[0] < 1e-6);
+            assert!((c - cos(x)).abs()[0] < 1e-6);
+        }
+    }
+
+    /// Cutoffs from 20 Hz to 20 kHz at 44.1 kHz, log-spaced
+    fn audible_cutoffs() -> impl Iterator<Item = f64> {
+        (0..=100_000).map(|i| 20. * 1000f64.powf(i as f64 * 1e-5))
+    }
+
+    #[test]
+    fn tan_cutoff_sweep() {
+        let max = max_error(
+            audible_cutoffs().map(|f| (core::f64::consts::PI * f / 44_100.) as f32),
+            |x| (tan(Simd::<f32, 1>::splat(x))[0] as f64 / (x as f64).tan() - 1.).abs(),
+        );
+        println!("tan, [20 Hz ; 20 kHz] at 44.1 kHz: max relative error {max:e}");
+        assert!(max < 3e-7);
+    }
+
+    /// Timing comparison against the mapped scalar `f32::tan`, run it with optimizations
+    /// (and e.g. `-C target-feature=+avx2,+fma`) and `--ignored --nocapture`
+    #[test]
+    #[ignore = "benchmark"]
+    fn tan_bench() {
+        use std::{hint::black_box, time::Instant};
+
+        // [0 ; 1.5), covering tan(w_c / 2) for all audible cutoffs
+        let x: Vec<VFloat> = (0..1024)
+            .map(|i| VFloat::splat(i as f32 * 1.5 / 1024.))
+            .collect();
+
+        let time = |name, f: fn(VFloat) -> VFloat| {
+            let start = Instant::now();
+            for _ in 0..1000 {
+                for &x in &x {
+                    black_box(f(black_box(x)));
+                }
+            }
+            let ns =
+                start.elapsed().as_nanos() as f64 / (1000 * x.len() * FLOATS_PER_VECTOR) as f64;
+            println!("{name}: {ns:.3} ns per lane");
+            ns
+        };
+
+        let simd = time("math::tan", tan);
+        let scalar = time("f32::tan", |x| map(x, f32::tan));
+        println!("speedup: {:.1}x", scalar / simd);
+    }
+
+    fn tanh_ulp_error(x: f32) -> f64 {
+        let exact = (x as f64).tanh();
+        (tanh(Simd::<f32, 1>::splat(x))[0] as f64 - exact).abs() / ulp(exact as f32)
+    }
+
+    #[test]
+    fn tanh_ulp() {
+        let max = max_error(
+            (-1_000_000..=1_000_000).map(|i| i as f32 * 1e-5),
+            tanh_ulp_error,
+        );
+        println!("tanh, [-10 ; 10]: max error {max} ULPs");
+        assert!(max <= 4.);
+
+        let max = max_error((-126..0).map(|i| 1.5 * 2f32.powi(i)), tanh_ulp_error);
+        assert!(max <= 4.);
+    }
+
+    #[test]
+    fn tanh_monotonic() {
+        let mut prev = -1.;
+        for i in -100_000..=100_000 {
+            let y = tanh(Simd::<f32, 1>::splat(i as f32 * 1e-4))[0];
+            assert!(y >= prev && y.abs() <= 1.);
+            prev = y;
+        }
+
+        // one float at a time, around many points of [0 ; 10]
+        for i in 0..=1000 {
+            let start = Simd::<f32, 8>::splat(i as f32 * 0.01);
+            let x = Simd::from_bits(start.to_bits() + Simd::from_array([0, 1, 2, 3, 4, 5, 6, 7]));
+            let y = tanh(x);
+
+            assert!(y.as_array().windows(2).all(|w| w[0] <= w[1]));
+            assert_eq!(tanh(-x).to_bits(), (-y).to_bits());
+        }
+
+        let x = Simd::from_array([
+            0.,
+            -0.,
+            8.7,
+            -20.,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            1e-40,
+            f32::NAN,
+        ]);
+        let y = tanh(x);
+        assert_eq!(
+            y.to_bits()[..7],
+            [0., -0., 1., -1., 1., -1., 1e-40].map(f32::to_bits)
+        );
+        assert!(y[7].is_nan());
+    }
+
+    #[test]
+    fn tanh_against_std() {
+        for i in -500_000..=500_000 {
+            let x = i as f32 * 1e-5;
+            let (y, expected) = (tanh(Simd::<f32, 1>::splat(x))[0], x.tanh());
+            assert!((y - expected).abs() as f64 <= 4. * ulp(expected));
+        }
+    }
+
+    /// Filter coefficients `g = tan(w_c / 2)`, against their exact values (in `f64`, from
+    /// the frequency in Hz). The error of `tan` is dominated by the rounding of `w_c` to
+    /// an `f32`, amplified near Nyquist, and stays below `2e-6`. `tan_half_x` stays
+    /// below `3e-5`.
+    #[test]
+    fn cutoff_coefficient_accuracy() {
+        let (mut max_tan, mut max_tan_half_x) = (0f64, 0f64);
+
+        for f in audible_cutoffs() {
+            let w_c = hz_to_w_c(Simd::<f32, 1>::splat(f as f32), 44_100.);
+            let exact = (core::f64::consts::PI * f / 44_100.).tan();
+
+            let g = tan(w_c * Simd::splat(0.5))[0] as f64;
+            max_tan = max_tan.max((g / exact - 1.).abs());
+
+            let g = tan_half_x(w_c)[0] as f64;
+            max_tan_half_x = max_tan_half_x.max((g / exact - 1.).abs());
+        }
+
+        println!("g: max relative error {max_tan:e} (tan), {max_tan_half_x:e} (tan_half_x)");
+        assert!(max_tan < 2e-6 && max_tan_half_x < 3e-5);
+    }
+
+    #[test]
+    fn atan_crossover() {
+        let inputs = [0.999_99, 1f32.next_down(), 1., 1f32.next_up(), 1.000_01];
+
+        for sign in [1., -1.] {
+            let x = Simd::from_array(inputs.map(|x| x * sign));
+            let y = atan(x).to_array();
+            for (&x, &y) in x.as_array().iter().zip(&y) {
+                assert!((y - x.atan()).abs() < 2e-6);
+            }
+            assert!(y.windows(2).all(|w| (w[1] - w[0]) * sign >= 0.));
+        }
+    }
+
+    #[test]
+    fn exp2_clamped_out_of_range() {
+        let v = Simd::from_array([f32::NAN, f32::NEG_INFINITY, -1e30, -126.]);
+        assert_eq!(exp2_clamped(v), Simd::splat(2f32.powi(-126)));
+
+        let v = Simd::from_array([f32::INFINITY, 1e30, 128., 127.]);
+        assert_eq!(exp2_clamped(v), Simd::splat(2f32.powi(127)));
+    }
+
+    #[test]
+    fn exp_exp10_accuracy() {
+        let mut max_exp = 0f64;
+        let mut max_exp10 = 0f64;
+
+        for i in 0..=10_000 {
+            let x = i as f32 * 0.004 - 20.;
+            let v = Simd::<f32, 1>::splat(x);
+
+            let y = exp_clamped(v)[0] as f64;
+            max_exp = max_exp.max((y / (x as f64).exp() - 1.).abs());
+
+            let y = exp10_clamped(v * Simd::splat(0.25))[0] as f64;
+            max_exp10 = max_exp10.max((y / 10f64.powf((x * 0.25) as f64) - 1.).abs());
+        }
+
+        println!("exp: max relative error {max_exp:e}, exp10: {max_exp10:e}");
+        assert!(max_exp < 5e-6 && max_exp10 < 5e-6);
+    }
+
+    #[test]
+    fn ln_log10_round_trip() {
+        for i in 0..=10_000 {
+            let x = Simd::<f32, 1>::splat(i as f32 * 0.004 - 20.);
+
+            assert!((ln(exp_clamped(x)) - x).abs()[0] < 1e-5);
+            assert!(
+                (log10(exp10_clamped(x * Simd::splat(0.25))) * Simd::splat(4.) - x).abs()[0] < 2e-5
+            );
+        }
+    }
+
+    #[test]
+    fn ln_log10_exp_exp10_against_f64() {
+        for i in 0..=40_000 {
+            let x = (i as f32 * 0.001 - 20.).exp2();
+            let v = Simd::<f32, 1>::splat(x);
+            let x = x as f64;
+
+            assert!((ln(v)[0] as f64 - x.ln()).abs() < 2e-6);
+            assert!((log10(v)[0] as f64 - x.log10()).abs() < 2e-6);
+
+            let (x_e, x_10) = unsafe { (exp(ln(v))[0] as f64, exp10(log10(v))[0] as f64) };
+            assert!((x_e / x - 1.).abs() < 1e-5);
+            assert!((x_10 / x - 1.).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn note_freq_conversions() {
+        let notes = Simd::from_array([69., 81., 57., 60.]);
+        let freqs = unsafe { note_to_freq(notes) }.to_array();
+
+        assert_eq!(freqs[..3], [440., 880., 220.]);
+        assert!((freqs[3] - 261.625_58).abs() < 1e-3);
+        assert_eq!(freq_to_note(Simd::from_array(freqs))[..3], notes[..3]);
+
+        for i in -240..=480 {
+            let note = Simd::<f32, 1>::splat(i as f32 * 0.5);
+            assert!((freq_to_note(unsafe { note_to_freq(note) }) - note).abs()[0] < 1e-3);
+        }
+    }
+
+    #[test]
+    fn cents_semitones_conversions() {
+        let cents = Simd::from_array([1200., -1200., 0., 2400.]);
+        let ratio = unsafe { cents_to_ratio(cents) };
+        assert!((ratio - Simd::from_array([2., 0.5, 1., 4.]))
+            .abs()
+            .simd_lt(Simd::splat(1e-6))
+            .all());
+        assert!((ratio_to_cents(ratio) - cents)
+            .abs()
+            .simd_lt(Simd::splat(1e-3))
+            .all());
+
+        for i in -480..=480 {
+            let semitones = Simd::<f32, 1>::splat(i as f32 * 0.1);
+            let ratio = unsafe { semitones_to_ratio_precise(semitones) };
+
+            assert!((ratio[0] as f64 / (semitones[0] as f64 / 12.).exp2() - 1.).abs() < 2e-7);
+            assert!((ratio_to_semitones(ratio) - semitones).abs()[0] < 2e-4);
+        }
+    }
+
+    #[test]
+    fn log2_grid() {
+        let inputs = (0..=20_000).map(|i| (i as f32 * 0.001 - 10.).exp2());
+        let max = max_error(inputs, log2_ulp_error);
+        println!("log2, [2^-10 ; 2^10]: max error {max} ULPs");
+        assert!(max <= LOG2_MAX_ULP);
+    }
+
+    #[test]
+    fn log2_powers_of_two() {
+        for i in -126..=127 {
+            assert_eq!(log2_scalar(2f32.powi(i)), i as f32);
+        }
+    }
+
+    #[test]
+    fn log2_subnormals() {
+        let x = Simd::from_array([
+            1.5,
+            f32::MIN_POSITIVE,
+            f32::MIN_POSITIVE * 0.75,
+            f32::from_bits(1),
+            3e-40,
+            0.,
+            1e-45,
+            f32::from_bits(1 << 9),
+        ]);
+
+        assert_eq!(
+            ilog2f(x),
+            Simd::from_array([0, -126, -127, -149, -132, -150, -149, -140]),
+        );
+
+        let expected = map(x, |x| {
+            if x == 0. {
+                -150.
+            } else {
+                (x as f64).log2() as f32
+            }
+        });
+        assert!((log2(x) - expected).abs().simd_lt(Simd::splat(1e-5)).all());
+    }
+
+    #[test]
+    fn log2_ieee_special_values() {
+        let v = Simd::from_array([
+            0.,
+            -0.,
+            -1.,
+            f32::INFINITY,
+            8.,
+            -f32::INFINITY,
+            f32::NAN,
+            0.5,
+        ]);
+        let y = log2_ieee(v).to_array();
+
+        assert_eq!(y[..2], [f32::NEG_INFINITY; 2]);
+        assert!(y[2].is_nan() && y[5].is_nan() && y[6].is_nan());
+        assert_eq!([y[3], y[4], y[7]], [f32::INFINITY, 3., -1.]);
+    }
+
+    #[test]
+    fn log2_around_powers_of_two() {
+        let inputs = (-20..=20).flat_map(|i| {
+            let p = 2f32.powi(i);
+            [p.next_down(), p.next_up()]
+        });
+
+        let max = max_error(inputs, log2_ulp_error);
+        println!("log2, around powers of two: max error {max} ULPs");
+        assert!(max <= LOG2_MAX_ULP);
+    }
 }
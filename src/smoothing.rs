@@ -1 +1,325 @@
+use super::*;
 
+use simd::cmp::{SimdPartialEq, SimdPartialOrd};
+
+/// Slew rate limiter. Moves towards its target by, at most, a fixed amount per
+/// sample, with separate rates when rising and falling, and never overshoots it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SlewSmoother<const N: usize>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    value: Simd<f32, N>,
+    target: Simd<f32, N>,
+    rise: Simd<f32, N>,
+    fall: Simd<f32, N>,
+}
+
+impl<const N: usize> SlewSmoother<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Sets the maximum increase (`rise`) and decrease (`fall`) of the
+    /// value per sample. Both must be non-negative.
+    #[inline]
+    pub fn set_rates(&mut self, rise: Simd<f32, N>, fall: Simd<f32, N>) {
+        self.rise = rise;
+        self.fall = fall;
+    }
+
+    #[inline]
+    pub fn set_target(&mut self, target: Simd<f32, N>) {
+        self.target = target;
+    }
+
+    #[inline]
+    pub fn set_val_instantly(&mut self, value: Simd<f32, N>) {
+        self.value = value;
+        self.target = value;
+    }
+
+    #[inline]
+    pub fn tick1(&mut self) {
+        let delta = self.target - self.value;
+        let step = delta.simd_clamp(-self.fall, self.rise);
+
+        // land exactly on the target when it's within reach
+        self.value = step.simd_eq(delta).select(self.target, self.value + step);
+    }
+
+    #[inline]
+    pub fn get_current(&self) -> Simd<f32, N> {
+        self.value
+    }
+}
+
+/// Evaluates the cubic Bézier curve with control points `0`, `c1`, `c2` and `1` at `s`, using
+/// de Casteljau's algorithm, returning its value and its derivative
+#[inline]
+fn bezier<const N: usize>(
+    c1: Simd<f32, N>,
+    c2: Simd<f32, N>,
+    s: Simd<f32, N>,
+) -> (Simd<f32, N>, Simd<f32, N>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let p1 = s * c1;
+    let p2 = math::lerp(c1, c2, s);
+    let p3 = math::lerp(c2, Simd::splat(1.), s);
+
+    let p12 = math::lerp(p1, p2, s);
+    let p23 = math::lerp(p2, p3, s);
+
+    (math::lerp(p12, p23, s), (p23 - p12) * Simd::splat(3.))
+}
+
+/// Moves towards its target over a fixed number of samples, following a cubic Bézier curve
+/// in the (time, value) plane, from `(0, start)` to `(1, target)`. The two inner control
+/// points are `(c1, start)` and `(c2, target)`, where `c1 <= c2` are fractions of the
+/// duration, in `[0 ; 1]`. `(1/3, 2/3)` gives a smoothstep, `(0, 0)` leaves the start
+/// value as fast as possible and eases into the target (snappy attack, smooth release),
+/// and `(1, 1)` does the opposite.
+///
+/// Each tick refines the previous position on the curve with a single Newton-Raphson step,
+/// so the value follows the curve to within `0.5 / num_samples` of its extent.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BezierSmoother<const N: usize>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    start: Simd<f32, N>,
+    end: Simd<f32, N>,
+    c1: Simd<f32, N>,
+    c2: Simd<f32, N>,
+    t: Simd<f32, N>,
+    s: Simd<f32, N>,
+    increment: Simd<f32, N>,
+    value: Simd<f32, N>,
+}
+
+impl<const N: usize> BezierSmoother<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    const SOLVER_ITERATIONS: usize = 10;
+
+    /// Creates a smoother with the given control points, see [`Self::set_control_points`]
+    #[inline]
+    pub fn new(c1: Simd<f32, N>, c2: Simd<f32, N>) -> Self {
+        let mut smoother = Self::default();
+        smoother.set_control_points(c1, c2);
+        smoother
+    }
+
+    /// Sets the time coordinates of the two inner control points of the curve, as
+    /// fractions of the duration, both must be in `[0 ; 1]`, with `c1 <= c2`
+    #[inline]
+    pub fn set_control_points(&mut self, c1: Simd<f32, N>, c2: Simd<f32, N>) {
+        self.c1 = c1;
+        self.c2 = c2;
+    }
+
+    /// Starts moving from the current value towards `target`, reaching it in `num_samples` ticks
+    #[inline]
+    pub fn set_target(&mut self, target: Simd<f32, N>, num_samples: f32) {
+        self.start = self.value;
+        self.end = target;
+        self.t = Simd::splat(0.);
+        self.increment = Simd::splat(num_samples.recip());
+        // tick1 can't start from the beginning of the curve, where the derivative
+        // of the time coordinate can be zero, so the first position is solved here
+        self.s = self.solve_time(self.increment);
+    }
+
+    #[inline]
+    pub fn set_val_instantly(&mut self, value: Simd<f32, N>) {
+        self.start = value;
+        self.end = value;
+        self.t = Simd::splat(1.);
+        self.s = Simd::splat(1.);
+        self.value = value;
+    }
+
+    /// Finds the curve parameter whose time coordinate is `t`. Since the control points
+    /// are in `[0 ; 1]`, the time coordinate never decreases, so this uses Newton-Raphson
+    /// iterations, falling back to bisection when they leave the bracket around the solution
+    #[inline]
+    fn solve_time(&self, t: Simd<f32, N>) -> Simd<f32, N> {
+        let (mut lo, mut hi) = (Simd::splat(0.), Simd::splat(1.));
+        let mut s = t;
+
+        for _ in 0..Self::SOLVER_ITERATIONS {
+            let (x, dx) = bezier(self.c1, self.c2, s);
+
+            let above = x.simd_gt(t);
+            lo = above.select(lo, s);
+            hi = above.select(s, hi);
+
+            // non-finite steps (from dx == 0) fail the bracket check, and fall back to bisection
+            let newton = s - (x - t) / dx;
+            let in_bracket = newton.simd_ge(lo) & newton.simd_le(hi);
+            s = in_bracket.select(newton, (lo + hi) * Simd::splat(0.5));
+        }
+
+        s
+    }
+
+    #[inline]
+    pub fn tick1(&mut self) {
+        let zero = Simd::splat(0.);
+        let one = Simd::splat(1.);
+        let t = self.t + self.increment;
+        // snap to the end despite the rounding errors accumulated by t
+        let t = (one - t)
+            .simd_lt(self.increment * Simd::splat(0.5))
+            .select(one, t);
+        self.t = t;
+
+        // With c1 <= c2, the time coordinate's derivative is only zero at the ends of the
+        // curve. The first position is solved in set_target, and the time coordinate is
+        // concave near the end, where Newton-Raphson steps undershoot instead of diverging
+        let (x, dx) = bezier(self.c1, self.c2, self.s);
+        let s = dx.simd_gt(zero).select(self.s + (t - x) / dx, self.s);
+        // never going backwards, to keep the value monotonic
+        let s = s.simd_clamp(self.s, one);
+        self.s = s;
+
+        // the value's control points are start, start, target and target
+        let (progress, _) = bezier(zero, one, s);
+
+        self.value = t
+            .simd_eq(one)
+            .select(self.end, math::lerp(self.start, self.end, progress));
+    }
+
+    #[inline]
+    pub fn get_current(&self) -> Simd<f32, N> {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slew_step_response() {
+        let rise = Simd::from_array([0.125, 0.01, 1., 0.3]);
+        let fall = Simd::from_array([0.0625, 0.02, 0.5, 0.7]);
+
+        // rounding error of values in the tested range
+        let eps = Simd::splat(1e-6);
+
+        let mut smoother = SlewSmoother::<4>::default();
+        smoother.set_rates(rise, fall);
+
+        for target in [3.3, -1.7, 0.] {
+            let target = Simd::splat(target);
+            smoother.set_target(target);
+
+            let mut prev = smoother.get_current();
+            for _ in 0..1000 {
+                smoother.tick1();
+                let value = smoother.get_current();
+
+                let slope = value - prev;
+                assert!(slope.simd_le(rise + eps).all(), "{slope:?}");
+                assert!((-slope).simd_le(fall + eps).all(), "{slope:?}");
+                prev = value;
+            }
+
+            assert_eq!(smoother.get_current(), target);
+        }
+    }
+
+    /// Finds `s` in `[0 ; 1]` such that `f(s) = y` by bisection. `f` must be increasing
+    fn bisect(f: impl Fn(f64) -> f64, y: f64) -> f64 {
+        let (mut lo, mut hi) = (0., 1.);
+        for _ in 0..60 {
+            let mid = (lo + hi) * 0.5;
+            if f(mid) < y {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        (lo + hi) * 0.5
+    }
+
+    #[test]
+    fn bezier_against_reference() {
+        let c1 = Simd::from_array([1. / 3., 0., 1., 0., 0.2, 0.05, 0.5, 0.6]);
+        let c2 = Simd::from_array([2. / 3., 0., 1., 1., 0.8, 0.3, 0.5, 0.95]);
+
+        let (start, end) = (Simd::splat(-2.), Simd::splat(6.));
+        let mut smoother = BezierSmoother::<8>::new(c1, c2);
+
+        for num_samples in [10, 100, 1000, 48_000] {
+            let tolerance = 0.5 / num_samples as f64;
+
+            smoother.set_val_instantly(start);
+            smoother.set_target(end, num_samples as f32);
+
+            let mut prev = start;
+            for _ in 0..num_samples {
+                smoother.tick1();
+                let value = smoother.get_current();
+                assert!(value.simd_ge(prev).all(), "{value:?}");
+                prev = value;
+
+                for i in 0..8 {
+                    let (c1, c2) = (c1[i] as f64, c2[i] as f64);
+                    let time = |s: f64| {
+                        let r = 1. - s;
+                        3. * r * s * (r * c1 + s * c2) + s * s * s
+                    };
+                    let progress = |s: f64| s * s * (3. - 2. * s);
+
+                    // the distance to the curve is at most the smallest of the vertical
+                    // and horizontal distances, which are very different near vertical
+                    // or horizontal tangents
+                    let (t, p) = (smoother.t[i] as f64, (value[i] as f64 + 2.) / 8.);
+                    let vertical = (progress(bisect(time, t)) - p).abs();
+                    let horizontal = (time(bisect(progress, p)) - t).abs();
+
+                    assert!(
+                        vertical.min(horizontal) < tolerance,
+                        "{num_samples} samples, lane {i}, t = {t}, value = {p}"
+                    );
+                }
+            }
+
+            assert_eq!(smoother.get_current(), end);
+
+            smoother.tick1();
+            assert_eq!(smoother.get_current(), end);
+        }
+    }
+
+    #[test]
+    fn bezier_control_point_timing() {
+        let mut smoother = BezierSmoother::new(
+            Simd::from_array([0., 1. / 3., 1., 0.]),
+            Simd::from_array([0., 2. / 3., 1., 0.]),
+        );
+        smoother.set_target(Simd::splat(1.), 100.);
+
+        for _ in 0..20 {
+            smoother.tick1();
+        }
+
+        // (0, 0) has a snappy attack, and (1, 1) a slow one. (1/3, 2/3) is a smoothstep
+        let value = smoother.get_current();
+        assert!(value[0] > 0.6 && value[2] < 0.02, "{value:?}");
+        assert!((value[1] - 0.104).abs() < 1e-5, "{value:?}");
+
+        // the target can change mid-transition, starting from the current value
+        smoother.set_target(Simd::splat(0.), 100.);
+        smoother.tick1();
+        assert!((smoother.get_current() - value)
+            .simd_le(Simd::splat(0.))
+            .all());
+        assert!(smoother.get_current()[3] > 0.);
+    }
+}
@@ -7,6 +7,9 @@ use core::{cell::Cell, mem};
 #[cfg(any(target_feature = "avx512f", target_feature = "avx2"))]
 use core::arch::x86_64::*;
 
+#[cfg(not(any(target_feature = "avx512f", target_feature = "avx2")))]
+use simd::ptr::SimdConstPtr;
+
 pub const MAX_VECTOR_WIDTH: usize = {
     if cfg!(target_feature = "avx512f") {
         64
@@ -44,7 +47,9 @@ where
 /// # Safety
 ///
 /// The same requirements as `Simd::gather_select_unchecked`
-// We're using intrinsics for now because u32 gathers aren't in core::simd (yet?)
+// We're using intrinsics for now because u32 gathers aren't in core::simd (yet?).
+// Elsewhere (e.g. NEON, which has no gather instructions), we gather through a
+// vector of pointers, to avoid going through a slice that doesn't cover the offsets
 #[inline]
 pub unsafe fn gather_select_unchecked(
     pointer: *const f32,
@@ -53,10 +58,9 @@ pub unsafe fn gather_select_unchecked(
     or: VFloat,
 ) -> VFloat {
     #[cfg(not(any(target_feature = "avx512f", target_feature = "avx2")))]
-    return Simd::gather_select_unchecked(
-        core::slice::from_raw_parts(pointer, 0),
+    return Simd::gather_select_ptr(
+        Simd::splat(pointer).wrapping_add(index.cast()),
         enable.cast(),
-        index.cast(),
         or,
     );
 
@@ -89,12 +93,7 @@ pub unsafe fn gather_select_unchecked(
 #[inline]
 pub unsafe fn gather_unchecked(pointer: *const f32, index: VUInt) -> VFloat {
     #[cfg(not(any(target_feature = "avx512f", target_feature = "avx2")))]
-    return Simd::gather_select_unchecked(
-        core::slice::from_raw_parts(pointer, 0),
-        Mask::splat(true),
-        index.cast(),
-        VFloat::splat(0.),
-    );
+    return Simd::gather_ptr(Simd::splat(pointer).wrapping_add(index.cast()));
 
     #[cfg(all(not(target_feature = "avx512f"), target_feature = "avx2"))]
     return _mm256_i32gather_ps(pointer, index.into(), 4).into();
@@ -103,6 +102,59 @@ pub unsafe fn gather_unchecked(pointer: *const f32, index: VUInt) -> VFloat {
     return _mm512_i32gather_ps(index.into(), pointer.cast(), 4).into();
 }
 
+/// Like `Simd::scatter_select_unchecked` but with a pointer and using `u32` offsets.
+/// If several enabled lanes share the same offset, the last one is written.
+///
+/// # Safety
+///
+/// The same requirements as `Simd::scatter_select_unchecked`, `pointer`
+/// must be valid for writes at all the enabled offsets
+// There are no scatter instructions below AVX-512, so we write lanes one by one there
+#[inline]
+pub unsafe fn scatter_select_unchecked(
+    pointer: *mut f32,
+    index: VUInt,
+    enable: TMask,
+    values: VFloat,
+) {
+    #[cfg(not(target_feature = "avx512f"))]
+    for ((i, v), enabled) in index
+        .to_array()
+        .into_iter()
+        .zip(values.to_array())
+        .zip(enable.to_array())
+    {
+        if enabled {
+            pointer.add(i as usize).write(v);
+        }
+    }
+
+    #[cfg(target_feature = "avx512f")]
+    _mm512_mask_i32scatter_ps(
+        pointer.cast(),
+        enable.to_bitmask() as __mmask16,
+        index.into(),
+        values.into(),
+        4,
+    );
+}
+
+/// Like `Simd::scatter_select_unchecked` but with a pointer, `u32` offsets and all offsets are enabled
+///
+/// # Safety
+///
+/// The same as [`scatter_select_unchecked`]
+#[inline]
+pub unsafe fn scatter_unchecked(pointer: *mut f32, index: VUInt, values: VFloat) {
+    #[cfg(not(target_feature = "avx512f"))]
+    for (i, v) in index.to_array().into_iter().zip(values.to_array()) {
+        pointer.add(i as usize).write(v);
+    }
+
+    #[cfg(target_feature = "avx512f")]
+    _mm512_i32scatter_ps(pointer.cast(), index.into(), values.into(), 4);
+}
+
 #[inline]
 pub fn sum_to_stereo_sample(x: VFloat) -> f32x2 {
     unsafe {
@@ -218,7 +270,8 @@ pub fn swap_stereo<T: SimdElement>(v: Simd<T, FLOATS_PER_VECTOR>) -> Simd<T, FLO
     simd_swizzle!(v, FLIP_PAIRS)
 }
 
-/// triangluar panning of a vector of stereo samples, given 0 <= pan <= 1
+/// triangluar panning of a vector of stereo samples, given 0 <= pan <= 1. Out of range
+/// pans extrapolate linearly, e.g. `pan = -0.1` gives weights of `1.1` and `-0.1`
 #[inline]
 pub fn triangular_pan_weights(pan_norm: VFloat) -> VFloat {
     const SIGN_MASK: VFloat = {
@@ -241,7 +294,19 @@ pub fn triangular_pan_weights(pan_norm: VFloat) -> VFloat {
         Simd::from_array(array)
     };
 
-    VFloat::from_bits(pan_norm.to_bits() ^ SIGN_MASK.to_bits()) + ALT_ONE
+    math::flip_sign(pan_norm, SIGN_MASK) + ALT_ONE
+}
+
+/// constant-power (-3 dB) panning of a vector of stereo samples, given 0 <= pan <= 1
+#[inline]
+pub fn constant_power_pan_weights(pan_norm: VFloat) -> VFloat {
+    // left weights are computed as sin((1 - pan) * pi/2), rather than cos(pan * pi/2),
+    // so that both channels go through the same polynomial, and are exactly equal
+    // at the center, and mirror each other around it
+    let (weights, _) = math::sin_cos(
+        triangular_pan_weights(pan_norm) * VFloat::splat(core::f32::consts::FRAC_PI_2),
+    );
+    weights
 }
 
 #[inline]
@@ -312,3 +377,103 @@ where
         Mask::splat(val)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lane_indices() -> VUInt {
+        Simd::from_array(core::array::from_fn(|i| i as u32))
+    }
+
+    #[test]
+    fn gather_known_indices() {
+        let table: Vec<f32> = (0..64).map(|i| i as f32 * 0.25).collect();
+
+        // reversed, and spread over the whole table
+        let index = VUInt::splat(63) - lane_indices() * VUInt::splat(63 / FLOATS_PER_VECTOR as u32);
+        let expected = Simd::from_array(index.to_array().map(|i| table[i as usize]));
+        assert_eq!(unsafe { gather_unchecked(table.as_ptr(), index) }, expected);
+
+        // disabled lanes aren't read, even with out of bounds offsets
+        let enable = TMask::from_array(core::array::from_fn(|i| i % 2 == 1));
+        let index = enable.select(index, VUInt::splat(u32::MAX));
+        let or = VFloat::splat(-1.);
+        assert_eq!(
+            unsafe { gather_select_unchecked(table.as_ptr(), index, enable, or) },
+            enable.select(expected, or),
+        );
+    }
+
+    #[test]
+    fn triangular_pan() {
+        let pans = [-0.125, 0., 0.25, 0.5, 1., 1.5];
+        let expected = [
+            [1.125, -0.125],
+            [1., 0.],
+            [0.75, 0.25],
+            [0.5, 0.5],
+            [0., 1.],
+            [-0.5, 1.5],
+        ];
+
+        for (pan, expected) in pans.into_iter().zip(expected) {
+            let weights = triangular_pan_weights(VFloat::splat(pan));
+
+            for weights in split_stereo(&weights) {
+                assert_eq!(weights.to_array(), expected, "pan = {pan}");
+            }
+        }
+    }
+
+    #[test]
+    fn constant_power_pan() {
+        for i in 0..=256 {
+            let pan = i as f32 / 256.;
+            let weights = constant_power_pan_weights(VFloat::splat(pan));
+            let mirrored = constant_power_pan_weights(VFloat::splat(1. - pan));
+            assert_eq!(swap_stereo(mirrored), weights);
+
+            for &[l, r] in split_stereo(&weights).map(Simd::to_array).iter() {
+                assert!((l * l + r * r - 1.).abs() < 1e-6);
+
+                if i == 128 {
+                    assert_eq!(l, r);
+                }
+            }
+        }
+
+        let weights = constant_power_pan_weights(VFloat::splat(0.));
+        assert_eq!(split_stereo(&weights)[0], Simd::from_array([1., 0.]));
+
+        let weights = constant_power_pan_weights(VFloat::splat(1.));
+        assert_eq!(split_stereo(&weights)[0], Simd::from_array([0., 1.]));
+    }
+
+    #[test]
+    fn scatter_gather_round_trip() {
+        let mut buffer = vec![0f32; 4 * FLOATS_PER_VECTOR];
+        let index = lane_indices() * VUInt::splat(3) + VUInt::splat(1);
+        let values = lane_indices().cast() + VFloat::splat(0.5);
+
+        unsafe { scatter_unchecked(buffer.as_mut_ptr(), index, values) };
+        assert_eq!(unsafe { gather_unchecked(buffer.as_ptr(), index) }, values);
+
+        // only even lanes are written, odd lanes keep the previous values
+        let enable = TMask::from_array(core::array::from_fn(|i| i % 2 == 0));
+        unsafe { scatter_select_unchecked(buffer.as_mut_ptr(), index, enable, -values) };
+        assert_eq!(
+            unsafe { gather_unchecked(buffer.as_ptr(), index) },
+            enable.select(-values, values),
+        );
+
+        // when several lanes share an offset, the last one is written
+        let index = lane_indices() / VUInt::splat(2);
+        unsafe { scatter_unchecked(buffer.as_mut_ptr(), index, values) };
+        let expected = Simd::from_array(core::array::from_fn(|i| values[i | 1]));
+        assert_eq!(
+            unsafe { gather_unchecked(buffer.as_ptr(), index) },
+            expected
+        );
+    }
+}